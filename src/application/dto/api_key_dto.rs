@@ -0,0 +1,51 @@
+use crate::domain::entities::api_key::ApiKey;
+use serde::{Deserialize, Serialize};
+
+// CreateApiKeyDto is the request body for creating an API key, e.g. `{"name": "ci", "scopes": ["read", "scrape"]}`.
+#[derive(Deserialize)]
+pub struct CreateApiKeyDto {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+// ApiKeyDto is used to transfer an API key over the API, without its secret.
+// It contains:
+// - id: The key id, used to revoke it.
+// - name: The label of the key.
+// - scopes: The scopes granted to the key.
+// - source: "config" for keys from API_KEYS or ADMIN_TOKEN, "repository" for keys created over the API.
+// - created_at: When the key was created (or loaded, for configured keys), formatted as "YYYY-MM-DD HH:MM:SS".
+#[derive(Serialize)]
+pub struct ApiKeyDto {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub source: String,
+    pub created_at: String,
+}
+
+impl ApiKeyDto {
+    // Converts a domain key into a DTO, recording where the key comes from.
+    pub fn new(api_key: ApiKey, source: &str) -> Self {
+        Self {
+            id: api_key.id,
+            name: api_key.name,
+            scopes: api_key
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str().to_string())
+                .collect(),
+            source: source.to_string(),
+            created_at: api_key.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+// CreatedApiKeyDto is returned once when a key is created.
+// It is the key itself plus its secret, which is not stored and cannot be retrieved again.
+#[derive(Serialize)]
+pub struct CreatedApiKeyDto {
+    #[serde(flatten)]
+    pub key: ApiKeyDto,
+    pub secret: String,
+}
@@ -0,0 +1,60 @@
+use crate::domain::entities::scraper_health::{CircuitBreakerStatus, ScraperHealth};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+// Formats an optional timestamp as "YYYY-MM-DD HH:MM:SS" for API responses.
+fn format_timestamp(timestamp: Option<NaiveDateTime>) -> Option<String> {
+    timestamp.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+// CircuitBreakerDto is used to transfer the state of the scraper's circuit breaker over the API.
+// It contains:
+// - state: One of "closed", "open" or "half_open".
+// - consecutive_failures: How many scrapes failed in a row.
+// - opened_at: When the circuit was last opened.
+// - retry_at: When the next probe scrape will be allowed (only while open).
+#[derive(Serialize)]
+pub struct CircuitBreakerDto {
+    pub state: String,
+    pub consecutive_failures: u32,
+    pub opened_at: Option<String>,
+    pub retry_at: Option<String>,
+}
+
+impl From<CircuitBreakerStatus> for CircuitBreakerDto {
+    // Converts the domain circuit breaker snapshot into a DTO for API responses.
+    fn from(status: CircuitBreakerStatus) -> Self {
+        Self {
+            state: status.state.as_str().to_string(),
+            consecutive_failures: status.consecutive_failures,
+            opened_at: format_timestamp(status.opened_at),
+            retry_at: format_timestamp(status.retry_at),
+        }
+    }
+}
+
+// ScraperHealthDto is used to transfer the health of the scraping service over the API.
+// It contains:
+// - status: Either "healthy" or "degraded".
+// - reasons: Why the scraper is considered degraded (empty when healthy).
+// - checked_at: When the health was last evaluated, formatted as "YYYY-MM-DD HH:MM:SS".
+// - circuit_breaker: The state of the circuit breaker guarding scrapes, if any.
+#[derive(Serialize)]
+pub struct ScraperHealthDto {
+    pub status: String,
+    pub reasons: Vec<String>,
+    pub checked_at: Option<String>,
+    pub circuit_breaker: Option<CircuitBreakerDto>,
+}
+
+impl From<ScraperHealth> for ScraperHealthDto {
+    // Converts the domain health snapshot into a DTO for API responses.
+    fn from(health: ScraperHealth) -> Self {
+        Self {
+            status: health.status.as_str().to_string(),
+            reasons: health.reasons,
+            checked_at: format_timestamp(health.checked_at),
+            circuit_breaker: health.circuit_breaker.map(CircuitBreakerDto::from),
+        }
+    }
+}
@@ -1,17 +1,34 @@
 use crate::domain::entities::holiday::Holiday;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 // HolidayDto is a Data Transfer Object (DTO) that is used to transfer holiday data over the API.
 // It includes:
 // - date: The holiday date as a string, formatted in a specific way.
 // - description: The description or name of the holiday.
 // - is_joint_leave: A boolean flag indicating whether the holiday is a joint leave (e.g., Cuti Bersama).
+// - category: The kind of holiday derived from the description, e.g. "national" or "islamic".
+// - is_manual: Whether the holiday was added or corrected by a manual correction (override).
+// The category and is_manual are ignored when a HolidayDto is received as input.
 #[derive(Serialize, Deserialize)]
 pub struct HolidayDto {
     pub date: String,
     pub description: String,
     pub is_joint_leave: bool,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub is_manual: bool,
+}
+
+impl HolidayDto {
+    // The names of the fields, as accepted by the `fields` query parameter.
+    pub const FIELDS: [&'static str; 5] = [
+        "date",
+        "description",
+        "is_joint_leave",
+        "category",
+        "is_manual",
+    ];
 }
 
 impl From<Holiday> for HolidayDto {
@@ -19,39 +36,121 @@ impl From<Holiday> for HolidayDto {
     // This is useful when you need to convert the domain model to a DTO for API responses.
     fn from(holiday: Holiday) -> Self {
         Self {
+            category: holiday.category().as_str().to_string(),
             date: holiday.format_date(),
             description: holiday.description,
             is_joint_leave: holiday.is_joint_leave,
+            is_manual: holiday.is_manual,
         }
     }
 }
 
-// GroupedHolidaysDto is used to transfer grouped holidays (e.g., joint and non-joint leave) over the API.
+// HolidayGroupDto is one group of holidays in a grouped listing.
 // It contains:
-// - joint_leave: A list of holidays that are joint leave.
-// - non_joint_leave: A list of holidays that are not joint leave.
+// - key: The group, e.g. "january", "friday", "q1", "joint_leave" or "islamic".
+// - count: The number of holidays in the group.
+// - holidays: The holidays in the group, ordered by date and trimmed to the selected fields.
+#[derive(Serialize)]
+pub struct HolidayGroupDto {
+    pub key: String,
+    pub count: usize,
+    pub holidays: Vec<serde_json::Value>,
+}
+
+// GroupedHolidaysDto is used to transfer grouped holidays over the API.
+// It contains:
+// - by: The attribute the holidays are grouped by ("type", "month", "weekday", "quarter" or "category").
+// - total: The number of holidays across all groups.
+// - groups: Every group in its natural order (e.g. January to December), including empty ones.
 #[derive(Serialize)]
 pub struct GroupedHolidaysDto {
-    pub joint_leave: Vec<HolidayDto>,
-    pub non_joint_leave: Vec<HolidayDto>,
+    pub by: String,
+    pub total: usize,
+    pub groups: Vec<HolidayGroupDto>,
 }
 
 impl GroupedHolidaysDto {
     // The new() function is a constructor for GroupedHolidaysDto.
-    // It takes two vectors of holidays (joint leave and non-joint leave), converts them to DTOs, and returns a GroupedHolidaysDto.
-    pub fn new(joint_leave: Vec<Holiday>, non_joint_leave: Vec<Holiday>) -> Self {
+    // It takes the grouping name and the groups of holidays, already converted to (trimmed) DTOs, and counts them.
+    pub fn new(by: &str, groups: Vec<(&str, Vec<serde_json::Value>)>) -> Self {
+        let groups: Vec<HolidayGroupDto> = groups
+            .into_iter()
+            .map(|(key, holidays)| HolidayGroupDto {
+                key: key.to_string(),
+                count: holidays.len(),
+                holidays,
+            })
+            .collect();
+
         Self {
-            joint_leave: joint_leave.into_iter().map(HolidayDto::from).collect(),
-            non_joint_leave: non_joint_leave.into_iter().map(HolidayDto::from).collect(),
+            by: by.to_string(),
+            total: groups.iter().map(|group| group.count).sum(),
+            groups,
         }
     }
+}
+
+// HolidaysByTypeDto is the original shape of a grouped listing, still returned when no grouping is asked for.
+// It contains:
+// - joint_leave: The joint leave days, trimmed to the selected fields.
+// - non_joint_leave: The other holidays, trimmed to the selected fields.
+#[derive(Serialize)]
+pub struct HolidaysByTypeDto {
+    pub joint_leave: Vec<serde_json::Value>,
+    pub non_joint_leave: Vec<serde_json::Value>,
+}
 
-    // The to_hashmap() function converts the grouped holidays into a HashMap.
-    // It groups the holidays by their type (joint leave or non-joint leave) and returns the result as a HashMap.
-    pub fn to_hashmap(self) -> HashMap<String, Vec<HolidayDto>> {
-        let mut result = HashMap::new();
-        result.insert("joint_leave".to_string(), self.joint_leave);
-        result.insert("non_joint_leave".to_string(), self.non_joint_leave);
-        result
+impl HolidaysByTypeDto {
+    // The new() function is a constructor for HolidaysByTypeDto.
+    // It takes the groups of a grouping by type, keyed "joint_leave" and "non_joint_leave".
+    pub fn new(groups: Vec<(&str, Vec<serde_json::Value>)>) -> Self {
+        let mut dto = Self {
+            joint_leave: Vec::new(),
+            non_joint_leave: Vec::new(),
+        };
+        for (key, holidays) in groups {
+            match key {
+                "joint_leave" => dto.joint_leave = holidays,
+                _ => dto.non_joint_leave = holidays,
+            }
+        }
+        dto
     }
 }
+
+// GroupedHolidaysResultDto is the response of a grouped listing.
+// - ByType: Without `by`, the original `{"joint_leave": [...], "non_joint_leave": [...]}` shape,
+//   so existing clients keep working.
+// - Grouped: With `by` (including `by=type`), every group with its key and count.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum GroupedHolidaysResultDto {
+    ByType(HolidaysByTypeDto),
+    Grouped(GroupedHolidaysDto),
+}
+
+// TodayHolidaysDto tells whether today is a holiday.
+// It contains:
+// - date: Today's date ("YYYY-MM-DD") in the requested time zone.
+// - timezone: The time zone "today" was determined in, e.g. "Asia/Makassar".
+// - is_holiday: Whether today is a holiday.
+// - holidays: The holidays today, empty if it is not a holiday.
+#[derive(Serialize)]
+pub struct TodayHolidaysDto {
+    pub date: String,
+    pub timezone: String,
+    pub is_holiday: bool,
+    pub holidays: Vec<HolidayDto>,
+}
+
+// UpcomingHolidaysDto lists the next holidays from today.
+// It contains:
+// - from: Today's date ("YYYY-MM-DD") in the requested time zone; holidays today are included.
+// - timezone: The time zone "today" was determined in.
+// - holidays: The next holidays, ordered by date and trimmed to the selected fields.
+#[derive(Serialize)]
+pub struct UpcomingHolidaysDto {
+    pub from: String,
+    pub timezone: String,
+    pub holidays: Vec<serde_json::Value>,
+}
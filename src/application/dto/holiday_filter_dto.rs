@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+// HolidayFilterDto holds the optional filters applied when listing holidays.
+// It contains:
+// - month: Only holidays in a month, 1 (January) to 12 (December).
+// - joint_leave: Only joint leave days (`true`) or only regular holidays (`false`).
+// - category: Only holidays of a category, e.g. "islamic" (see `HolidayCategory`).
+// - q: Only holidays whose description contains the text, ignoring case, diacritics and punctuation.
+// - weekday: Only holidays on the given weekdays, comma-separated, e.g. "fri" or "senin,jumat".
+// - exclude_weekends: Leave out holidays on a Saturday or Sunday.
+// - fields: Only include the given fields in each holiday, comma-separated, e.g. "date,description".
+#[derive(Deserialize, Default)]
+pub struct HolidayFilterDto {
+    pub month: Option<u32>,
+    pub joint_leave: Option<bool>,
+    pub category: Option<String>,
+    pub q: Option<String>,
+    pub weekday: Option<String>,
+    pub exclude_weekends: Option<bool>,
+    pub fields: Option<String>,
+}
@@ -0,0 +1,49 @@
+use crate::domain::entities::holiday_override::HolidayOverride;
+use serde::{Deserialize, Serialize};
+
+// HolidayCorrectionDto is the request body of the admin endpoints that add, edit or remove a holiday.
+// It contains:
+// - date: The date of the holiday, in "YYYY-MM-DD" format.
+// - match_description: For edit and remove, only the holiday with this description is affected;
+//   every holiday on the date if omitted.
+// - description: The description of the added holiday, or the new description of the edited one.
+// - is_joint_leave: Whether the added or edited holiday is a joint leave.
+#[derive(Deserialize)]
+pub struct HolidayCorrectionDto {
+    pub date: String,
+    pub match_description: Option<String>,
+    pub description: Option<String>,
+    pub is_joint_leave: Option<bool>,
+}
+
+// HolidayOverrideDto is used to transfer a stored manual override over the API.
+// It contains the override id (used to withdraw it), its action ("add", "edit" or "remove"),
+// the correction itself and when it was made, formatted as "YYYY-MM-DD HH:MM:SS".
+#[derive(Serialize)]
+pub struct HolidayOverrideDto {
+    pub id: String,
+    pub action: String,
+    pub date: String,
+    pub match_description: Option<String>,
+    pub description: Option<String>,
+    pub is_joint_leave: Option<bool>,
+    pub created_at: String,
+}
+
+impl From<HolidayOverride> for HolidayOverrideDto {
+    // Converts the domain override into a DTO.
+    fn from(holiday_override: HolidayOverride) -> Self {
+        Self {
+            id: holiday_override.id,
+            action: holiday_override.action.as_str().to_string(),
+            date: holiday_override.date.format("%Y-%m-%d").to_string(),
+            match_description: holiday_override.match_description,
+            description: holiday_override.description,
+            is_joint_leave: holiday_override.is_joint_leave,
+            created_at: holiday_override
+                .created_at
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::domain::entities::scheduled_job::JobRun;
+use serde::Serialize;
+
+// JobRunDto is used to transfer a run of a scheduled job over the API.
+// It contains:
+// - id: The run id.
+// - job: The name of the job, e.g. "scrape-current-year".
+// - scheduled_for / started_at / finished_at: When the run was due, started and finished,
+//   formatted as "YYYY-MM-DD HH:MM:SS".
+// - duration_ms: How long the run took, in milliseconds.
+// - status: One of "succeeded", "failed" or "skipped".
+// - message: What the run did, or why it failed or was skipped.
+#[derive(Serialize)]
+pub struct JobRunDto {
+    pub id: String,
+    pub job: String,
+    pub scheduled_for: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: i64,
+    pub status: String,
+    pub message: String,
+}
+
+impl From<JobRun> for JobRunDto {
+    // Converts the domain run into a DTO, formatting its timestamps.
+    fn from(run: JobRun) -> Self {
+        let format = |t: chrono::NaiveDateTime| t.format("%Y-%m-%d %H:%M:%S").to_string();
+        Self {
+            id: run.id,
+            job: run.job,
+            scheduled_for: format(run.scheduled_for),
+            started_at: format(run.started_at),
+            finished_at: format(run.finished_at),
+            duration_ms: (run.finished_at - run.started_at).num_milliseconds(),
+            status: run.status.as_str().to_string(),
+            message: run.message,
+        }
+    }
+}
+
+// QueuedJobRunDto is a run of a scheduled job that was requested over the API and waits to be run.
+// It contains:
+// - id: The id the run is recorded under in the run history once it has run.
+// - job: The name of the job.
+// - requested_at: When the run was requested, formatted as "YYYY-MM-DD HH:MM:SS".
+// - status: Always "queued".
+#[derive(Serialize)]
+pub struct QueuedJobRunDto {
+    pub id: String,
+    pub job: String,
+    pub requested_at: String,
+    pub status: String,
+}
+
+impl QueuedJobRunDto {
+    // Creates the DTO of a queued run.
+    pub fn new(id: String, job: &str, requested_at: chrono::NaiveDateTime) -> Self {
+        Self {
+            id,
+            job: job.to_string(),
+            requested_at: requested_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            status: "queued".to_string(),
+        }
+    }
+}
+
+// ScheduledJobStatusDto is the state of one scheduled job.
+// It contains:
+// - job: The name of the job.
+// - schedule: Its cron expression.
+// - next_run: When the job is next due, or null if the scheduler is disabled or the schedule never matches again.
+// - last_run: The most recent run of the job, or null if it never ran.
+#[derive(Serialize)]
+pub struct ScheduledJobStatusDto {
+    pub job: String,
+    pub schedule: String,
+    pub next_run: Option<String>,
+    pub last_run: Option<JobRunDto>,
+}
+
+// SchedulerStatusDto is used to transfer the state of the scheduler over the API.
+// It contains:
+// - enabled: Whether the scheduler runs the jobs on their schedules (SCHEDULER_ENABLED).
+// - leader: Whether this instance is the elected leader; of several replicas, only the leader runs the jobs.
+// - timezone: The time zone of the schedules and run times.
+// - jobs: Every scheduled job, in configuration order.
+#[derive(Serialize)]
+pub struct SchedulerStatusDto {
+    pub enabled: bool,
+    pub leader: bool,
+    pub timezone: String,
+    pub jobs: Vec<ScheduledJobStatusDto>,
+}
@@ -1,2 +1,10 @@
+pub mod api_key_dto;
 pub mod api_response;
-pub mod holiday_dto;
\ No newline at end of file
+pub mod health_dto;
+pub mod holiday_dto;
+pub mod holiday_filter_dto;
+pub mod holiday_override_dto;
+pub mod job_run_dto;
+pub mod scrape_job_dto;
+pub mod scrape_report_dto;
+pub mod usage_dto;
@@ -0,0 +1,65 @@
+use crate::application::dto::scrape_report_dto::{MergeConflictDto, YearScrapeResultDto};
+use crate::domain::entities::scrape_job::{JobStatus, ScrapeJob};
+use serde::{Deserialize, Serialize};
+
+// SubmitScrapeJobDto is the request body for submitting a scrape job, e.g. `{"year": 2025}`.
+// An optional merge strategy can be given, e.g. `{"year": 2025, "strategy": "add-only"}`.
+#[derive(Deserialize)]
+pub struct SubmitScrapeJobDto {
+    pub year: i32,
+    pub strategy: Option<String>,
+}
+
+// ScrapeJobDto is used to transfer the state of a scrape job over the API.
+// It contains:
+// - id: The job id, used to poll `/jobs/{id}`.
+// - year: The year being scraped.
+// - strategy: The requested merge strategy, or null for the default one.
+// - status: One of "queued", "running", "succeeded" or "failed".
+// - created_at / started_at / finished_at: Lifecycle timestamps, formatted as "YYYY-MM-DD HH:MM:SS".
+// - report: The scrape outcome once the job has finished.
+#[derive(Serialize)]
+pub struct ScrapeJobDto {
+    pub id: String,
+    pub year: i32,
+    pub strategy: Option<String>,
+    pub status: String,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub report: Option<YearScrapeResultDto>,
+}
+
+impl From<ScrapeJob> for ScrapeJobDto {
+    // Converts the domain job into a DTO, attaching the scrape report for finished jobs.
+    fn from(job: ScrapeJob) -> Self {
+        let format = |t: chrono::NaiveDateTime| t.format("%Y-%m-%d %H:%M:%S").to_string();
+        let report = match job.status {
+            JobStatus::Succeeded => Some(YearScrapeResultDto::scraped(
+                job.year,
+                job.holidays_scraped.unwrap_or_default(),
+                job.conflicts
+                    .iter()
+                    .cloned()
+                    .map(MergeConflictDto::from)
+                    .collect(),
+            )),
+            JobStatus::Failed => Some(YearScrapeResultDto::failed(
+                job.year,
+                job.error.clone().unwrap_or_default(),
+            )),
+            JobStatus::Queued | JobStatus::Running => None,
+        };
+
+        Self {
+            id: job.id,
+            year: job.year,
+            strategy: job.strategy.map(|s| s.as_str().to_string()),
+            status: job.status.as_str().to_string(),
+            created_at: format(job.created_at),
+            started_at: job.started_at.map(format),
+            finished_at: job.finished_at.map(format),
+            report,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::domain::entities::holiday_merge::MergeConflict;
+use serde::{Deserialize, Serialize};
+
+// MergeConflictDto reports a scraped holiday that the manual corrections (overrides) of its date remove or change.
+// It contains:
+// - date: The date in question, formatted as "YYYY-MM-DD".
+// - manual: The descriptions on that date with the corrections applied.
+// - scraped: The description of the scraped holiday.
+// - resolution: "kept_manual" if the corrections win, "review_manual" if they still win but should be reviewed.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MergeConflictDto {
+    pub date: String,
+    pub manual: Vec<String>,
+    pub scraped: String,
+    pub resolution: String,
+}
+
+impl From<MergeConflict> for MergeConflictDto {
+    fn from(conflict: MergeConflict) -> Self {
+        Self {
+            date: conflict.date.format("%Y-%m-%d").to_string(),
+            manual: conflict.manual,
+            scraped: conflict.scraped,
+            resolution: conflict.resolution,
+        }
+    }
+}
+
+// YearScrapeResultDto describes what happened to a single year during a batch scrape.
+// It contains:
+// - year: The year that was processed.
+// - status: One of "scraped", "skipped" (data already present), "unpublished" (the source has no holidays for
+//   the year yet) or "failed".
+// - holidays: The number of holidays scraped (only for "scraped").
+// - error: The error message (only for "unpublished" and "failed").
+// - conflicts: The conflicts with manual corrections (only for "scraped", omitted if there are none).
+#[derive(Serialize, Deserialize, Clone)]
+pub struct YearScrapeResultDto {
+    pub year: i32,
+    pub status: String,
+    pub holidays: Option<usize>,
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<MergeConflictDto>,
+}
+
+impl YearScrapeResultDto {
+    // Creates the result for a year that was scraped successfully.
+    pub fn scraped(year: i32, holidays: usize, conflicts: Vec<MergeConflictDto>) -> Self {
+        Self {
+            year,
+            status: "scraped".to_string(),
+            holidays: Some(holidays),
+            error: None,
+            conflicts,
+        }
+    }
+
+    // Creates the result for a year that was skipped because its data already exists.
+    pub fn skipped(year: i32) -> Self {
+        Self {
+            year,
+            status: "skipped".to_string(),
+            holidays: None,
+            error: None,
+            conflicts: Vec::new(),
+        }
+    }
+
+    // Creates the result for a year the source has not published holidays for yet.
+    pub fn unpublished(year: i32, error: String) -> Self {
+        Self {
+            year,
+            status: "unpublished".to_string(),
+            holidays: None,
+            error: Some(error),
+            conflicts: Vec::new(),
+        }
+    }
+
+    // Creates the result for a year whose scrape failed.
+    pub fn failed(year: i32, error: String) -> Self {
+        Self {
+            year,
+            status: "failed".to_string(),
+            holidays: None,
+            error: Some(error),
+            conflicts: Vec::new(),
+        }
+    }
+}
+
+// ScrapeReportDto summarises a batch scrape over a range of years.
+// It contains:
+// - from / to: The inclusive range of years that was requested.
+// - scraped / skipped / unpublished / failed: How many years ended in each outcome.
+// - years: The per-year outcome, ordered by year.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScrapeReportDto {
+    pub from: i32,
+    pub to: i32,
+    pub scraped: usize,
+    pub skipped: usize,
+    pub unpublished: usize,
+    pub failed: usize,
+    pub years: Vec<YearScrapeResultDto>,
+}
+
+impl ScrapeReportDto {
+    // Builds the report from per-year results, sorting them by year and counting each outcome.
+    pub fn new(from: i32, to: i32, mut years: Vec<YearScrapeResultDto>) -> Self {
+        years.sort_by_key(|result| result.year);
+        let count = |status: &str| years.iter().filter(|r| r.status == status).count();
+
+        Self {
+            from,
+            to,
+            scraped: count("scraped"),
+            skipped: count("skipped"),
+            unpublished: count("unpublished"),
+            failed: count("failed"),
+            years,
+        }
+    }
+}
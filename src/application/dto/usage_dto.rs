@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+// ClientUsageDto counts the requests of one client to one scope on a day.
+// It contains:
+// - client: "key:<id>" for requests with an API key, "ip:<address>" for anonymous requests.
+// - scope: The scope of the route group, e.g. "read" or "scrape".
+// - requests: The number of requests that were let through.
+// - limited: The number of requests that were rejected by the rate limiter.
+#[derive(Serialize)]
+pub struct ClientUsageDto {
+    pub client: String,
+    pub scope: String,
+    pub requests: u64,
+    pub limited: u64,
+}
+
+// UsageReportDto lists the usage of every client on a day, busiest client first.
+#[derive(Serialize)]
+pub struct UsageReportDto {
+    pub date: String,
+    pub clients: Vec<ClientUsageDto>,
+}
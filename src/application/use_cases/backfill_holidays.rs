@@ -0,0 +1,166 @@
+use crate::application::dto::scrape_report_dto::{ScrapeReportDto, YearScrapeResultDto};
+use crate::application::use_cases::scrape_holidays::ScrapeHolidaysUseCase;
+use crate::domain::repositories::holiday_repository::HolidayRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::is_valid_year;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+// BackfillHolidaysUseCase scrapes a range of years, e.g. to fill in historical data.
+// Years that already have data are skipped, and at most `concurrency` years are scraped at once.
+pub struct BackfillHolidaysUseCase {
+    holiday_repository: Arc<dyn HolidayRepository>,
+    scrape_use_case: Arc<ScrapeHolidaysUseCase>,
+    concurrency: usize,
+}
+
+impl BackfillHolidaysUseCase {
+    // Constructor to initialize BackfillHolidaysUseCase with the repository, the single-year scrape use case
+    // and the maximum number of concurrent scrapes.
+    pub fn new(
+        holiday_repository: Arc<dyn HolidayRepository>,
+        scrape_use_case: Arc<ScrapeHolidaysUseCase>,
+        concurrency: usize,
+    ) -> Self {
+        Self {
+            holiday_repository,
+            scrape_use_case,
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    // Scrapes every year in the inclusive range `from..=to` and reports the outcome per year.
+    // Failures of individual years do not abort the batch. A year the source has no holidays for yet (NotFound)
+    // is reported as unpublished rather than failed.
+    pub async fn execute(&self, from: i32, to: i32) -> AppResult<ScrapeReportDto> {
+        if !is_valid_year(from) || !is_valid_year(to) {
+            return Err(AppError::BadRequest(format!(
+                "Years must be between 1900 and 2100, got {} to {}",
+                from, to
+            )));
+        }
+        if from > to {
+            return Err(AppError::BadRequest(format!(
+                "'from' ({}) must not be after 'to' ({})",
+                from, to
+            )));
+        }
+
+        println!("Starting batch scrape for years {} to {}", from, to);
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+        let mut years_by_task = HashMap::new();
+        let mut results = Vec::new();
+
+        for year in from..=to {
+            // Skip years that are already present; a failing check is reported rather than guessed.
+            match self.holiday_repository.holidays_exist_for_year(year).await {
+                Ok(true) => {
+                    results.push(YearScrapeResultDto::skipped(year));
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    results.push(YearScrapeResultDto::failed(year, e.to_string()));
+                    continue;
+                }
+            }
+
+            let semaphore = semaphore.clone();
+            let use_case = self.scrape_use_case.clone();
+            let task = tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                match use_case.scrape(year, use_case.default_strategy()).await {
+                    Ok(outcome) => YearScrapeResultDto::scraped(
+                        year,
+                        outcome.holidays.len(),
+                        outcome.conflicts.into_iter().map(Into::into).collect(),
+                    ),
+                    Err(e @ AppError::NotFound(_)) => {
+                        YearScrapeResultDto::unpublished(year, e.to_string())
+                    }
+                    Err(e) => YearScrapeResultDto::failed(year, e.to_string()),
+                }
+            });
+            years_by_task.insert(task.id(), year);
+        }
+
+        // A task that panicked is reported as a failed year, so every year of the range shows up in the report.
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok(year_result) => results.push(year_result),
+                Err(e) => {
+                    eprintln!("Batch scrape task failed: {}", e);
+                    if let Some(year) = years_by_task.get(&e.id()) {
+                        results.push(YearScrapeResultDto::failed(
+                            *year,
+                            format!("Scrape task failed: {}", e),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let report = ScrapeReportDto::new(from, to, results);
+        println!(
+            "Batch scrape for years {} to {} finished: {} scraped, {} skipped, {} unpublished, {} failed",
+            from, to, report.scraped, report.skipped, report.unpublished, report.failed
+        );
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::holiday::Holiday;
+    use crate::domain::entities::holiday_merge::MergeStrategy;
+    use crate::domain::services::holiday_service::HolidayScrapingService;
+    use crate::infrastructure::persistence::file_override_repository::FileHolidayOverrideRepository;
+    use crate::infrastructure::persistence::file_repository::FileHolidayRepository;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+
+    // Has holidays for 2025 only, as if 2026 was not published yet.
+    struct PublishedUntil2025;
+
+    #[async_trait]
+    impl HolidayScrapingService for PublishedUntil2025 {
+        async fn scrape_holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+            if year > 2025 {
+                return Err(AppError::NotFound(format!(
+                    "No holidays found for {}",
+                    year
+                )));
+            }
+            let date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+            Ok(vec![Holiday::new(date, "Tahun Baru".to_string(), false)])
+        }
+    }
+
+    #[tokio::test]
+    async fn a_year_without_holidays_is_reported_as_unpublished() {
+        let data_dir = std::env::temp_dir().join(format!("backfill-{}", uuid::Uuid::new_v4()));
+        let data_dir = data_dir.to_string_lossy().into_owned();
+        let holidays = Arc::new(FileHolidayRepository::new(data_dir.clone()));
+        let scrape_use_case = Arc::new(ScrapeHolidaysUseCase::new(
+            holidays.clone(),
+            Arc::new(FileHolidayOverrideRepository::new(data_dir.clone())),
+            Arc::new(PublishedUntil2025),
+            MergeStrategy::MergeKeepManual,
+        ));
+        let use_case = BackfillHolidaysUseCase::new(holidays, scrape_use_case, 2);
+
+        let report = use_case.execute(2025, 2026).await.unwrap();
+        let _ = std::fs::remove_dir_all(&data_dir);
+
+        assert_eq!(report.scraped, 1);
+        assert_eq!(report.unpublished, 1);
+        assert_eq!(report.failed, 0);
+        assert_eq!(report.years[1].status, "unpublished");
+    }
+}
@@ -0,0 +1,29 @@
+use crate::application::dto::health_dto::ScraperHealthDto;
+use crate::domain::services::holiday_service::HolidayScrapingService;
+use crate::shared::errors::app_error::AppResult;
+use std::sync::Arc;
+
+// GetHealthUseCase reports the health of the components the API depends on,
+// currently the scraping service and its view of the external holiday site.
+pub struct GetHealthUseCase {
+    scraping_service: Arc<dyn HolidayScrapingService>,
+}
+
+impl GetHealthUseCase {
+    // Constructor to initialize GetHealthUseCase with the scraping service.
+    pub fn new(scraping_service: Arc<dyn HolidayScrapingService>) -> Self {
+        Self { scraping_service }
+    }
+
+    // Returns the current health of the scraping service as a DTO.
+    pub fn execute(&self) -> ScraperHealthDto {
+        ScraperHealthDto::from(self.scraping_service.health())
+    }
+
+    // Accepts the pending layout of the external site as the new baseline after an operator has checked
+    // that scrapes still extract the right holidays, and returns the health afterwards.
+    pub async fn accept_layout(&self) -> AppResult<ScraperHealthDto> {
+        self.scraping_service.accept_layout().await?;
+        Ok(self.execute())
+    }
+}
@@ -1,31 +1,145 @@
-use crate::application::dto::holiday_dto::{GroupedHolidaysDto, HolidayDto};
+use crate::application::dto::holiday_dto::{
+    GroupedHolidaysDto, GroupedHolidaysResultDto, HolidayDto, HolidaysByTypeDto, TodayHolidaysDto,
+    UpcomingHolidaysDto,
+};
+use crate::application::dto::holiday_filter_dto::HolidayFilterDto;
+use crate::domain::entities::holiday::Holiday;
+use crate::domain::entities::holiday_category::HolidayCategory;
+use crate::domain::entities::holiday_override::HolidayOverride;
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
 use crate::domain::repositories::holiday_repository::HolidayRepository;
-use crate::domain::services::holiday_service::HolidayDomainService;
+use crate::domain::services::holiday_service::{HolidayDomainService, HolidayGrouping};
 use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::{
+    is_valid_year, is_weekend, parse_date, parse_timezone, parse_weekday, today_in,
+};
+use crate::shared::utils::text_utils::normalize_for_search;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use chrono_tz::Tz;
+use serde_json::Value;
 use std::sync::Arc;
 
+// How many upcoming holidays are returned by default, and at most.
+const DEFAULT_UPCOMING_LIMIT: usize = 5;
+const MAX_UPCOMING_LIMIT: usize = 50;
+
+// How far ahead upcoming holidays are looked up.
+const UPCOMING_WINDOW_DAYS: i64 = 366;
+
+// ParsedFilter is a HolidayFilterDto with its values validated and parsed.
+struct ParsedFilter {
+    month: Option<u32>,
+    joint_leave: Option<bool>,
+    category: Option<HolidayCategory>,
+    query: Option<String>,
+    weekdays: Option<Vec<Weekday>>,
+    exclude_weekends: bool,
+}
+
+impl ParsedFilter {
+    // Parses the filter, returning a BadRequest error for values that are out of range or unknown.
+    fn parse(filter: &HolidayFilterDto) -> AppResult<Self> {
+        if let Some(month) = filter.month {
+            if !(1..=12).contains(&month) {
+                return Err(AppError::BadRequest(format!(
+                    "Month must be between 1 and 12, got {}",
+                    month
+                )));
+            }
+        }
+
+        let category = match filter.category.as_deref() {
+            Some(value) => Some(HolidayCategory::parse(value).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "Unknown category '{}', expected one of: {}",
+                    value,
+                    HolidayCategory::ALL.map(|c| c.as_str()).join(", ")
+                ))
+            })?),
+            None => None,
+        };
+
+        let weekdays = match filter.weekday.as_deref() {
+            Some(value) => Some(
+                value
+                    .split(',')
+                    .map(|day| {
+                        parse_weekday(day).ok_or_else(|| {
+                            AppError::BadRequest(format!("Unknown weekday '{}'", day.trim()))
+                        })
+                    })
+                    .collect::<AppResult<Vec<Weekday>>>()?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            month: filter.month,
+            joint_leave: filter.joint_leave,
+            category,
+            query: filter
+                .q
+                .as_deref()
+                .map(normalize_for_search)
+                .filter(|q| !q.trim().is_empty()),
+            weekdays,
+            exclude_weekends: filter.exclude_weekends.unwrap_or(false),
+        })
+    }
+
+    // Checks whether a holiday passes every filter that is set.
+    fn matches(&self, holiday: &Holiday) -> bool {
+        self.month.is_none_or(|month| holiday.date.month() == month)
+            && self
+                .joint_leave
+                .is_none_or(|joint_leave| holiday.is_joint_leave() == joint_leave)
+            && self
+                .category
+                .is_none_or(|category| holiday.category() == category)
+            && self.query.as_ref().is_none_or(|query| {
+                normalize_for_search(&holiday.description).contains(query.trim())
+            })
+            && self
+                .weekdays
+                .as_ref()
+                .is_none_or(|weekdays| weekdays.contains(&holiday.date.weekday()))
+            && !(self.exclude_weekends && is_weekend(&holiday.date))
+    }
+}
+
 // GetHolidaysUseCase struct is responsible for retrieving holidays from the repository
-// and returning them as DTOs, either as a simple list or grouped by type (e.g., joint leave vs. non-joint leave).
+// and returning them as DTOs, either as a simple list or grouped (e.g., by type, month or category).
+// Manual overrides are merged over the scraped holidays before anything is returned.
+// "Today" is determined in the service time zone unless a request asks for another one.
 pub struct GetHolidaysUseCase {
     holiday_repository: Arc<dyn HolidayRepository>,
+    override_repository: Arc<dyn HolidayOverrideRepository>,
     holiday_service: HolidayDomainService,
+    timezone: Tz,
 }
 
 impl GetHolidaysUseCase {
-    // Constructor to initialize GetHolidaysUseCase with the holiday repository.
-    // The holiday service is initialized with a default constructor.
-    pub fn new(holiday_repository: Arc<dyn HolidayRepository>) -> Self {
+    // Constructor to initialize GetHolidaysUseCase with the holiday and override repositories
+    // and the service time zone. The holiday service is initialized with a default constructor.
+    pub fn new(
+        holiday_repository: Arc<dyn HolidayRepository>,
+        override_repository: Arc<dyn HolidayOverrideRepository>,
+        timezone: Tz,
+    ) -> Self {
         Self {
             holiday_repository,
+            override_repository,
             holiday_service: HolidayDomainService::new(),
+            timezone,
         }
     }
 
-    // Method to fetch holidays for a given year and return them as a simple list of HolidayDto.
-    // If no holidays are found, it returns a NotFound error.
-    pub async fn execute(&self, year: i32) -> AppResult<Vec<HolidayDto>> {
-        // Fetch holidays from the repository
-        let holidays = self.holiday_repository.get_holidays_by_year(year).await?;
+    // Method to fetch holidays for a given year and return them as a simple list of HolidayDto,
+    // keeping only those that match the filter and trimming each to the selected fields.
+    // If the year has no holidays, it returns a NotFound error.
+    pub async fn execute(&self, year: i32, filter: &HolidayFilterDto) -> AppResult<Vec<Value>> {
+        // Fetch holidays from the repository, with the manual overrides applied
+        let holidays = self.holidays_for_year(year).await?;
 
         // If no holidays are found, return a NotFound error
         if holidays.is_empty() {
@@ -35,17 +149,35 @@ impl GetHolidaysUseCase {
             )));
         }
 
-        // Convert each Holiday to a HolidayDto
-        let holiday_dtos: Vec<HolidayDto> = holidays.into_iter().map(HolidayDto::from).collect();
-
-        Ok(holiday_dtos)
+        // Apply the filters, then convert each Holiday to a HolidayDto with the selected fields
+        let holidays = Self::apply_filter(holidays, filter)?;
+        Self::select_fields(holidays, filter)
     }
 
-    // Method to fetch holidays for a given year and return them grouped by holiday type (e.g., joint leave vs. non-joint leave).
-    // If no holidays are found, it returns a NotFound error.
-    pub async fn execute_grouped(&self, year: i32) -> AppResult<GroupedHolidaysDto> {
-        // Fetch holidays from the repository
-        let holidays = self.holiday_repository.get_holidays_by_year(year).await?;
+    // Method to fetch holidays for a given year and return them grouped by the given attribute
+    // ("type", "month", "weekday", "quarter" or "category"). Without a grouping, the holidays are split by type
+    // in the original `joint_leave` / `non_joint_leave` shape.
+    // Only holidays that match the filter are grouped, each trimmed to the selected fields.
+    // If the year has no holidays, it returns a NotFound error.
+    pub async fn execute_grouped(
+        &self,
+        year: i32,
+        by: Option<&str>,
+        filter: &HolidayFilterDto,
+    ) -> AppResult<GroupedHolidaysResultDto> {
+        let grouping = match by {
+            Some(value) => HolidayGrouping::parse(value).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "Unknown grouping '{}', expected one of: {}",
+                    value,
+                    HolidayGrouping::ALL.map(|g| g.as_str()).join(", ")
+                ))
+            })?,
+            None => HolidayGrouping::Type,
+        };
+
+        // Fetch holidays from the repository, with the manual overrides applied
+        let holidays = self.holidays_for_year(year).await?;
 
         // If no holidays are found, return a NotFound error
         if holidays.is_empty() {
@@ -55,9 +187,329 @@ impl GetHolidaysUseCase {
             )));
         }
 
-        // Use the holiday service to group holidays by type (joint leave vs non-joint leave)
-        let (joint_leave, non_joint_leave) = self.holiday_service.group_holidays_by_type(holidays);
+        // Use the holiday service to group the matching holidays
+        let holidays = Self::apply_filter(holidays, filter)?;
+        let groups = self
+            .holiday_service
+            .group_holidays(holidays, grouping)
+            .into_iter()
+            .map(|(key, holidays)| Ok((key, Self::select_fields(holidays, filter)?)))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        // Without `by`, keep the original shape of this endpoint
+        if by.is_none() {
+            return Ok(GroupedHolidaysResultDto::ByType(HolidaysByTypeDto::new(
+                groups,
+            )));
+        }
+        Ok(GroupedHolidaysResultDto::Grouped(GroupedHolidaysDto::new(
+            grouping.as_str(),
+            groups,
+        )))
+    }
+
+    // Method to list every year that has holiday data or manual overrides, in ascending order.
+    pub async fn list_available_years(&self) -> AppResult<Vec<i32>> {
+        let mut years = self.holiday_repository.list_available_years().await?;
+        years.extend(self.override_repository.list_override_years().await?);
+        years.sort();
+        years.dedup();
+        Ok(years)
+    }
+
+    // Method to fetch the holidays between two dates ("YYYY-MM-DD", both inclusive), across years,
+    // keeping only those that match the filter and trimming each to the selected fields.
+    // An empty list is returned if there are no matching holidays in the range.
+    pub async fn execute_range(
+        &self,
+        from: &str,
+        to: &str,
+        filter: &HolidayFilterDto,
+    ) -> AppResult<Vec<Value>> {
+        let from = parse_date(from)?;
+        let to = parse_date(to)?;
+        if from > to {
+            return Err(AppError::BadRequest(format!(
+                "'from' ({}) must not be after 'to' ({})",
+                from, to
+            )));
+        }
+
+        if !is_valid_year(from.year()) || !is_valid_year(to.year()) {
+            return Err(AppError::BadRequest(format!(
+                "Dates must be between 1900 and 2100, got {} to {}",
+                from, to
+            )));
+        }
+
+        let holidays = self
+            .holiday_repository
+            .get_holidays_in_range(from, to)
+            .await?;
+        let holidays = self.merge_overrides(holidays, from, to).await?;
+        let holidays = Self::apply_filter(holidays, filter)?;
+
+        Self::select_fields(holidays, filter)
+    }
+
+    // Method to fetch the holidays on a specific date ("YYYY-MM-DD").
+    // If the date is not a holiday, it returns a NotFound error.
+    pub async fn find_by_date(&self, date: &str) -> AppResult<Vec<HolidayDto>> {
+        let date = parse_date(date)?;
+        let holidays = self.holiday_repository.find_by_date(date).await?;
+        let holidays = self.merge_overrides(holidays, date, date).await?;
+
+        if holidays.is_empty() {
+            return Err(AppError::NotFound(format!("{} is not a holiday", date)));
+        }
+
+        Ok(holidays.into_iter().map(HolidayDto::from).collect())
+    }
+
+    // Method to tell whether today is a holiday, with "today" taken in the given time zone
+    // (an IANA name or WIB, WITA or WIT), or in the service time zone if None.
+    // If the year has neither scraped data nor overrides, it returns a NotFound error rather than answering
+    // that today is not a holiday.
+    pub async fn execute_today(&self, tz: Option<&str>) -> AppResult<TodayHolidaysDto> {
+        let timezone = self.resolve_timezone(tz)?;
+        let today = today_in(timezone);
+
+        let has_data = self
+            .holiday_repository
+            .holidays_exist_for_year(today.year())
+            .await?
+            || !self
+                .override_repository
+                .list_overrides(today.year())
+                .await?
+                .is_empty();
+        if !has_data {
+            return Err(AppError::NotFound(format!(
+                "No holiday data for year {} yet, so it is unknown whether {} is a holiday",
+                today.year(),
+                today
+            )));
+        }
+
+        let holidays = self.holiday_repository.find_by_date(today).await?;
+        let holidays = self.merge_overrides(holidays, today, today).await?;
 
-        Ok(GroupedHolidaysDto::new(joint_leave, non_joint_leave))
+        Ok(TodayHolidaysDto {
+            date: today.format("%Y-%m-%d").to_string(),
+            timezone: timezone.name().to_string(),
+            is_holiday: !holidays.is_empty(),
+            holidays: holidays.into_iter().map(HolidayDto::from).collect(),
+        })
+    }
+
+    // Method to fetch the next holidays from today (inclusive), with "today" taken in the given time zone
+    // or in the service time zone if None. At most `limit` holidays matching the filter are returned,
+    // looking up to a year ahead.
+    pub async fn execute_upcoming(
+        &self,
+        tz: Option<&str>,
+        limit: Option<usize>,
+        filter: &HolidayFilterDto,
+    ) -> AppResult<UpcomingHolidaysDto> {
+        let limit = limit.unwrap_or(DEFAULT_UPCOMING_LIMIT);
+        if !(1..=MAX_UPCOMING_LIMIT).contains(&limit) {
+            return Err(AppError::BadRequest(format!(
+                "Limit must be between 1 and {}, got {}",
+                MAX_UPCOMING_LIMIT, limit
+            )));
+        }
+
+        let timezone = self.resolve_timezone(tz)?;
+        let from = today_in(timezone);
+        let to = from + Duration::days(UPCOMING_WINDOW_DAYS);
+
+        let holidays = self
+            .holiday_repository
+            .get_holidays_in_range(from, to)
+            .await?;
+        let holidays = self.merge_overrides(holidays, from, to).await?;
+        let mut holidays = Self::apply_filter(holidays, filter)?;
+        holidays.sort_by_key(|holiday| holiday.date);
+        holidays.truncate(limit);
+
+        Ok(UpcomingHolidaysDto {
+            from: from.format("%Y-%m-%d").to_string(),
+            timezone: timezone.name().to_string(),
+            holidays: Self::select_fields(holidays, filter)?,
+        })
+    }
+
+    // Returns the requested time zone, or the service time zone if none was requested.
+    fn resolve_timezone(&self, tz: Option<&str>) -> AppResult<Tz> {
+        match tz.filter(|tz| !tz.trim().is_empty()) {
+            Some(tz) => parse_timezone(tz),
+            None => Ok(self.timezone),
+        }
+    }
+
+    // Returns the holidays of a year with its manual overrides applied.
+    // A year without scraped data is served from its overrides alone, if it has any.
+    async fn holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+        let overrides = self.override_repository.list_overrides(year).await?;
+        let holidays = match self.holiday_repository.get_holidays_by_year(year).await {
+            Ok(holidays) => holidays,
+            Err(AppError::NotFound(_)) if !overrides.is_empty() => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(self.holiday_service.apply_overrides(holidays, &overrides))
+    }
+
+    // Applies the manual overrides of every year between two dates to the holidays in that range.
+    // Overrides only affect their own date, so those outside the range are dropped.
+    async fn merge_overrides(
+        &self,
+        mut holidays: Vec<Holiday>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<Holiday>> {
+        for year in self.override_repository.list_override_years().await? {
+            if year < from.year() || year > to.year() {
+                continue;
+            }
+            let overrides: Vec<HolidayOverride> = self
+                .override_repository
+                .list_overrides(year)
+                .await?
+                .into_iter()
+                .filter(|holiday_override| {
+                    holiday_override.date >= from && holiday_override.date <= to
+                })
+                .collect();
+            holidays = self.holiday_service.apply_overrides(holidays, &overrides);
+        }
+
+        Ok(holidays)
+    }
+
+    // Keeps the holidays matching every filter that is set.
+    // Returns a BadRequest error if a filter value is invalid.
+    fn apply_filter(holidays: Vec<Holiday>, filter: &HolidayFilterDto) -> AppResult<Vec<Holiday>> {
+        let filter = ParsedFilter::parse(filter)?;
+        Ok(holidays
+            .into_iter()
+            .filter(|holiday| filter.matches(holiday))
+            .collect())
+    }
+
+    // Converts holidays to DTOs, keeping only the fields listed in the filter (all fields if none are listed).
+    // Returns a BadRequest error if an unknown field is requested.
+    fn select_fields(holidays: Vec<Holiday>, filter: &HolidayFilterDto) -> AppResult<Vec<Value>> {
+        let fields: Option<Vec<&str>> = filter.fields.as_deref().map(|fields| {
+            fields
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .collect()
+        });
+        if let Some(unknown) = fields
+            .iter()
+            .flatten()
+            .find(|field| !HolidayDto::FIELDS.contains(field))
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown field '{}', expected any of: {}",
+                unknown,
+                HolidayDto::FIELDS.join(", ")
+            )));
+        }
+
+        holidays
+            .into_iter()
+            .map(|holiday| {
+                let mut value = serde_json::to_value(HolidayDto::from(holiday))?;
+                if let (Some(fields), Value::Object(object)) = (&fields, &mut value) {
+                    object.retain(|key, _| fields.contains(&key.as_str()));
+                }
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holiday(month: u32, day: u32, description: &str, is_joint_leave: bool) -> Holiday {
+        Holiday::new(
+            NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+            description.to_string(),
+            is_joint_leave,
+        )
+    }
+
+    #[test]
+    fn parsed_filter_matches_every_set_filter() {
+        let filter = ParsedFilter::parse(&HolidayFilterDto {
+            month: Some(3),
+            joint_leave: Some(false),
+            category: Some("Islamic".to_string()),
+            q: Some("idul".to_string()),
+            weekday: Some("mon,jumat".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // Monday, March 31st
+        assert!(filter.matches(&holiday(3, 31, "Hari Raya Idul Fitri", false)));
+        assert!(!filter.matches(&holiday(3, 31, "Hari Raya Idul Fitri", true)));
+        assert!(!filter.matches(&holiday(4, 1, "Hari Raya Idul Fitri", false)));
+        // Saturday, March 29th
+        assert!(!filter.matches(&holiday(3, 29, "Hari Raya Idul Fitri", false)));
+    }
+
+    #[test]
+    fn parsed_filter_searches_ignoring_case_and_diacritics() {
+        let filter = ParsedFilter::parse(&HolidayFilterDto {
+            q: Some("ISRA MI'RAJ".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(filter.matches(&holiday(1, 27, "Isra Mi'raj Nabi Muhammad", false)));
+        assert!(!filter.matches(&holiday(1, 1, "Tahun Baru", false)));
+    }
+
+    #[test]
+    fn parsed_filter_excludes_weekends() {
+        let filter = ParsedFilter::parse(&HolidayFilterDto {
+            exclude_weekends: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(filter.matches(&holiday(1, 1, "Tahun Baru", false)));
+        assert!(!filter.matches(&holiday(3, 29, "Nyepi", false)));
+    }
+
+    #[test]
+    fn parsed_filter_rejects_invalid_values() {
+        let invalid = [
+            HolidayFilterDto {
+                month: Some(13),
+                ..Default::default()
+            },
+            HolidayFilterDto {
+                category: Some("pagan".to_string()),
+                ..Default::default()
+            },
+            HolidayFilterDto {
+                weekday: Some("fri,funday".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        for filter in &invalid {
+            assert!(matches!(
+                ParsedFilter::parse(filter),
+                Err(AppError::BadRequest(_))
+            ));
+        }
     }
 }
@@ -0,0 +1,238 @@
+use crate::application::dto::api_key_dto::{ApiKeyDto, CreateApiKeyDto, CreatedApiKeyDto};
+use crate::domain::entities::api_key::{ApiKey, ApiKeyScope};
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::now_in;
+use chrono_tz::Tz;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Prefix of a configured secret that is given as a SHA-256 hash rather than in plain text.
+const HASHED_SECRET_PREFIX: &str = "sha256:";
+
+// Access is the outcome of checking a request's API key against a required scope.
+// - Granted: The request may proceed; `key_id` is the id of the presented key, None for anonymous requests, and
+//   `scopes` are the scopes of that key, or the public scopes for anonymous requests.
+// - MissingKey: The scope is not public and the request has no key.
+// - InvalidKey: The key is unknown.
+// - InsufficientScope: The key is valid but does not grant the scope.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Access {
+    Granted {
+        key_id: Option<String>,
+        scopes: Vec<ApiKeyScope>,
+    },
+    MissingKey,
+    InvalidKey,
+    InsufficientScope,
+}
+
+// ManageApiKeysUseCase authenticates API keys and manages the keys created over the API.
+// Keys come from two places:
+// - the configuration (API_KEYS, and ADMIN_TOKEN as a key with the admin scope), fixed for the lifetime of the process;
+// - the API key repository, for keys created and revoked at runtime.
+// Requests without a key are granted the public scopes (PUBLIC_SCOPES).
+pub struct ManageApiKeysUseCase {
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    config_keys: Vec<ApiKey>,
+    public_scopes: Vec<ApiKeyScope>,
+    timezone: Tz,
+}
+
+impl ManageApiKeysUseCase {
+    // Constructor to initialize ManageApiKeysUseCase with the key repository and the configured keys.
+    // `api_keys` is a comma-separated list of `name:scopes:secret` entries, where scopes are joined by "+"
+    // and the secret may be given as "sha256:<hex>" instead of in plain text.
+    // Creation times are recorded in the given service time zone.
+    // Returns a Configuration error if API_KEYS or PUBLIC_SCOPES is invalid.
+    pub fn new(
+        api_key_repository: Arc<dyn ApiKeyRepository>,
+        api_keys: Option<&str>,
+        admin_token: Option<&str>,
+        public_scopes: &str,
+        timezone: Tz,
+    ) -> AppResult<Self> {
+        let now = now_in(timezone);
+        let mut config_keys = Vec::new();
+
+        for entry in api_keys
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let mut parts = entry.splitn(3, ':');
+            let (Some(name), Some(scopes), Some(secret)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(AppError::Configuration(
+                    "Invalid API_KEYS entry, expected name:scopes:secret".to_string(),
+                ));
+            };
+            let key_hash = match secret.strip_prefix(HASHED_SECRET_PREFIX) {
+                Some(hash) => hash.trim().to_lowercase(),
+                None => ApiKey::hash_secret(secret),
+            };
+
+            config_keys.push(ApiKey {
+                id: format!("config:{}", name.trim()),
+                name: name.trim().to_string(),
+                key_hash,
+                scopes: Self::parse_scopes(scopes.split('+'))
+                    .map_err(|e| e.for_setting("API_KEYS"))?,
+                created_at: now,
+            });
+        }
+
+        if let Some(admin_token) = admin_token {
+            config_keys.push(ApiKey {
+                id: "config:admin-token".to_string(),
+                name: "admin-token".to_string(),
+                key_hash: ApiKey::hash_secret(admin_token),
+                scopes: vec![ApiKeyScope::Admin],
+                created_at: now,
+            });
+        }
+
+        Ok(Self {
+            api_key_repository,
+            config_keys,
+            public_scopes: Self::parse_scopes(
+                public_scopes.split(',').filter(|s| !s.trim().is_empty()),
+            )
+            .map_err(|e| e.for_setting("PUBLIC_SCOPES"))?,
+            timezone,
+        })
+    }
+
+    // Checks whether a request presenting the given key (if any) may use the required scope.
+    pub async fn authorize(
+        &self,
+        secret: Option<&str>,
+        required: ApiKeyScope,
+    ) -> AppResult<Access> {
+        let Some(secret) = secret else {
+            return Ok(
+                if self
+                    .public_scopes
+                    .iter()
+                    .any(|scope| scope.grants(required))
+                {
+                    Access::Granted {
+                        key_id: None,
+                        scopes: self.public_scopes.clone(),
+                    }
+                } else {
+                    Access::MissingKey
+                },
+            );
+        };
+
+        let key_hash = ApiKey::hash_secret(secret);
+        let api_key = match self.config_keys.iter().find(|key| key.key_hash == key_hash) {
+            Some(api_key) => Some(api_key.clone()),
+            None => self.api_key_repository.find_by_hash(&key_hash).await?,
+        };
+
+        Ok(match api_key {
+            Some(api_key) if api_key.allows(required) => Access::Granted {
+                key_id: Some(api_key.id),
+                scopes: api_key.scopes,
+            },
+            Some(_) => Access::InsufficientScope,
+            None => Access::InvalidKey,
+        })
+    }
+
+    // Creates a key with the given name and scopes.
+    // The secret is returned once and only its hash is stored.
+    pub async fn create_key(&self, request: CreateApiKeyDto) -> AppResult<CreatedApiKeyDto> {
+        let name = request.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::BadRequest(
+                "API key name must not be empty".to_string(),
+            ));
+        }
+        let scopes = Self::parse_scopes(request.scopes.iter().map(String::as_str))?;
+        if scopes.is_empty() {
+            return Err(AppError::BadRequest(
+                "At least one scope is required".to_string(),
+            ));
+        }
+
+        let secret = format!("cik_{}", Uuid::new_v4().simple());
+        let api_key = ApiKey {
+            id: Uuid::new_v4().to_string(),
+            name,
+            key_hash: ApiKey::hash_secret(&secret),
+            scopes,
+            created_at: now_in(self.timezone),
+        };
+        self.api_key_repository.save_key(api_key.clone()).await?;
+
+        println!("Created API key {} ({})", api_key.id, api_key.name);
+        Ok(CreatedApiKeyDto {
+            key: ApiKeyDto::new(api_key, "repository"),
+            secret,
+        })
+    }
+
+    // Lists the configured keys followed by the keys created over the API.
+    pub async fn list_keys(&self) -> AppResult<Vec<ApiKeyDto>> {
+        let stored = self.api_key_repository.list_keys().await?;
+        Ok(self
+            .config_keys
+            .iter()
+            .cloned()
+            .map(|api_key| ApiKeyDto::new(api_key, "config"))
+            .chain(
+                stored
+                    .into_iter()
+                    .map(|api_key| ApiKeyDto::new(api_key, "repository")),
+            )
+            .collect())
+    }
+
+    // Revokes a key created over the API. Configured keys can only be removed from the configuration.
+    pub async fn revoke_key(&self, id: &str) -> AppResult<()> {
+        if self.config_keys.iter().any(|api_key| api_key.id == id) {
+            return Err(AppError::BadRequest(format!(
+                "API key {} is configured in API_KEYS or ADMIN_TOKEN and cannot be revoked over the API",
+                id
+            )));
+        }
+
+        self.api_key_repository.delete_key(id).await?;
+        println!("Revoked API key {}", id);
+        Ok(())
+    }
+
+    // Checks whether any configured or stored key grants the admin scope.
+    pub async fn has_admin_key(&self) -> AppResult<bool> {
+        let stored = self.api_key_repository.list_keys().await?;
+        Ok(self
+            .config_keys
+            .iter()
+            .chain(stored.iter())
+            .any(|api_key| api_key.allows(ApiKeyScope::Admin)))
+    }
+
+    // Parses scope names, rejecting unknown ones. Duplicates are dropped.
+    fn parse_scopes<'a>(names: impl Iterator<Item = &'a str>) -> AppResult<Vec<ApiKeyScope>> {
+        let mut scopes = Vec::new();
+        for name in names {
+            let scope = ApiKeyScope::parse(name).ok_or_else(|| {
+                let expected: Vec<&str> = ApiKeyScope::ALL.iter().map(|s| s.as_str()).collect();
+                AppError::BadRequest(format!(
+                    "Invalid scope '{}', expected one of: {}",
+                    name.trim(),
+                    expected.join(", ")
+                ))
+            })?;
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        Ok(scopes)
+    }
+}
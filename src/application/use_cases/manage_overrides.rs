@@ -0,0 +1,140 @@
+use crate::application::dto::holiday_override_dto::{HolidayCorrectionDto, HolidayOverrideDto};
+use crate::domain::entities::holiday_override::{HolidayOverride, OverrideAction};
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::{is_valid_year, now_in, parse_date};
+use chrono::Datelike;
+use chrono_tz::Tz;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// ManageOverridesUseCase records manual corrections (add, edit, remove) to the holidays of a year.
+// Corrections are stored as overrides and merged over the scraped data by GetHolidaysUseCase,
+// so they survive later scrapes of the year.
+pub struct ManageOverridesUseCase {
+    override_repository: Arc<dyn HolidayOverrideRepository>,
+    timezone: Tz,
+}
+
+impl ManageOverridesUseCase {
+    // Constructor to initialize ManageOverridesUseCase with the override repository and the service time zone,
+    // in which the overrides' creation times are recorded.
+    pub fn new(override_repository: Arc<dyn HolidayOverrideRepository>, timezone: Tz) -> Self {
+        Self {
+            override_repository,
+            timezone,
+        }
+    }
+
+    // Adds a holiday to the year. The description is required.
+    pub async fn add_holiday(
+        &self,
+        year: i32,
+        correction: HolidayCorrectionDto,
+    ) -> AppResult<HolidayOverrideDto> {
+        if correction
+            .description
+            .as_deref()
+            .is_none_or(|description| description.trim().is_empty())
+        {
+            return Err(AppError::BadRequest(
+                "A description is required to add a holiday".to_string(),
+            ));
+        }
+        self.record(year, OverrideAction::Add, correction).await
+    }
+
+    // Edits the matching holidays of a date. A new description or joint leave flag is required.
+    pub async fn edit_holiday(
+        &self,
+        year: i32,
+        correction: HolidayCorrectionDto,
+    ) -> AppResult<HolidayOverrideDto> {
+        if correction.description.is_none() && correction.is_joint_leave.is_none() {
+            return Err(AppError::BadRequest(
+                "A new description or is_joint_leave is required to edit a holiday".to_string(),
+            ));
+        }
+        self.record(year, OverrideAction::Edit, correction).await
+    }
+
+    // Removes the matching holidays of a date.
+    pub async fn remove_holiday(
+        &self,
+        year: i32,
+        correction: HolidayCorrectionDto,
+    ) -> AppResult<HolidayOverrideDto> {
+        self.record(year, OverrideAction::Remove, correction).await
+    }
+
+    // Lists the overrides of a year, oldest first.
+    pub async fn list_overrides(&self, year: i32) -> AppResult<Vec<HolidayOverrideDto>> {
+        Self::validate_year(year)?;
+        let overrides = self.override_repository.list_overrides(year).await?;
+        Ok(overrides
+            .into_iter()
+            .map(HolidayOverrideDto::from)
+            .collect())
+    }
+
+    // Withdraws an override of the year, so the scraped data of its date is served again.
+    pub async fn delete_override(&self, year: i32, id: &str) -> AppResult<()> {
+        Self::validate_year(year)?;
+        self.override_repository.delete_override(year, id).await
+    }
+
+    // Validates the correction against the year and stores it as an override.
+    async fn record(
+        &self,
+        year: i32,
+        action: OverrideAction,
+        correction: HolidayCorrectionDto,
+    ) -> AppResult<HolidayOverrideDto> {
+        Self::validate_year(year)?;
+        let date = parse_date(&correction.date)?;
+        if date.year() != year {
+            return Err(AppError::BadRequest(format!(
+                "Date {} is not in year {}",
+                date, year
+            )));
+        }
+
+        let trim = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let holiday_override = HolidayOverride {
+            id: Uuid::new_v4().to_string(),
+            year,
+            date,
+            action,
+            match_description: trim(correction.match_description),
+            description: trim(correction.description),
+            is_joint_leave: correction.is_joint_leave,
+            created_at: now_in(self.timezone),
+        };
+        self.override_repository
+            .save_override(holiday_override.clone())
+            .await?;
+
+        println!(
+            "Recorded {} override {} for {}",
+            action.as_str(),
+            holiday_override.id,
+            date
+        );
+        Ok(HolidayOverrideDto::from(holiday_override))
+    }
+
+    // Checks that the year is within the supported range.
+    fn validate_year(year: i32) -> AppResult<()> {
+        if !is_valid_year(year) {
+            return Err(AppError::BadRequest(format!(
+                "Year must be between 1900 and 2100, got {}",
+                year
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,2 +1,9 @@
+pub mod backfill_holidays;
+pub mod get_health;
 pub mod get_holidays;
-pub mod scrape_holidays;
\ No newline at end of file
+pub mod manage_api_keys;
+pub mod manage_overrides;
+pub mod rate_limit;
+pub mod scheduled_jobs;
+pub mod scrape_holidays;
+pub mod scrape_jobs;
@@ -0,0 +1,436 @@
+use crate::application::dto::usage_dto::{ClientUsageDto, UsageReportDto};
+use crate::domain::entities::api_key::ApiKeyScope;
+use crate::shared::errors::app_error::AppResult;
+use crate::shared::utils::date_utils::{parse_date, today_in};
+use chrono::{Days, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// How many days of usage counters are kept.
+const USAGE_RETENTION_DAYS: i64 = 30;
+
+// Number of buckets above which idle (full) buckets are dropped.
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+// How many client and scope counters are kept per day. Beyond it, the least used counter is folded into the
+// OTHER_CLIENTS counter of its scope, so the usage of a day stays bounded however many clients show up.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+// The client the counters folded beyond MAX_TRACKED_CLIENTS are counted under.
+const OTHER_CLIENTS: &str = "other";
+
+// RateDecision is the outcome of a rate limit check.
+// - Allowed: The request may proceed.
+// - Limited: The client has to wait `retry_after` before its next request is allowed.
+// - QuotaExceeded: The client used up its daily quota and has to wait `retry_after`, until the next day.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RateDecision {
+    Allowed,
+    Limited { retry_after: Duration },
+    QuotaExceeded { retry_after: Duration },
+}
+
+// TokenBucket holds the tokens of one client for one scope.
+// Tokens refill continuously at `per_minute / 60` per second, up to a capacity of `per_minute`.
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+// UsageCounter counts the requests of a client to a scope on a day.
+#[derive(Default)]
+struct UsageCounter {
+    requests: u64,
+    limited: u64,
+}
+
+impl UsageCounter {
+    // The requests counted, let through or rejected.
+    fn total(&self) -> u64 {
+        self.requests + self.limited
+    }
+}
+
+// The usage counters of one day, per client and scope.
+type DailyUsage = HashMap<(String, ApiKeyScope), UsageCounter>;
+
+// RateLimitUseCase limits the request rate of every client and counts its daily usage.
+// Every IP address first gets a token bucket checked before its API key is looked at, so floods of requests
+// with made-up keys are cut off without authenticating them. Each client (API key, or IP address for anonymous
+// requests) then gets a token bucket per scope, sized by the per-minute limit of the scope, and optionally a
+// daily quota of requests across all scopes. A limit or quota of 0 disables it.
+// Buckets and counters are kept in memory, so they are per process and reset on restart.
+// Usage and quotas are counted per day in the service time zone, for at most MAX_TRACKED_CLIENTS clients a day:
+// the least used ones are folded into an "other" client, which keeps the heavy users' quotas enforced.
+pub struct RateLimitUseCase {
+    limits: HashMap<ApiKeyScope, u32>,
+    ip_per_minute: u32,
+    daily_quota: u64,
+    timezone: Tz,
+    // Keyed by client and scope; the per-IP buckets checked before authentication have no scope.
+    buckets: Mutex<HashMap<(String, Option<ApiKeyScope>), TokenBucket>>,
+    usage: Mutex<BTreeMap<NaiveDate, DailyUsage>>,
+}
+
+impl RateLimitUseCase {
+    // Constructor to initialize RateLimitUseCase with the per-minute limit of each scope, the per-minute limit
+    // of an IP address before authentication, the daily quota of a client, and the time zone whose days
+    // the usage is counted in.
+    pub fn new(
+        read_per_minute: u32,
+        scrape_per_minute: u32,
+        admin_per_minute: u32,
+        ip_per_minute: u32,
+        daily_quota: u64,
+        timezone: Tz,
+    ) -> Self {
+        Self {
+            limits: HashMap::from([
+                (ApiKeyScope::Read, read_per_minute),
+                (ApiKeyScope::Scrape, scrape_per_minute),
+                (ApiKeyScope::Admin, admin_per_minute),
+            ]),
+            ip_per_minute,
+            daily_quota,
+            timezone,
+            buckets: Mutex::new(HashMap::new()),
+            usage: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    // Takes a token from the IP address's bucket, before the request is authenticated.
+    // Only rejected requests are recorded in today's usage (for the scope of the route); the others are
+    // recorded by `check` once the client is known.
+    pub fn check_ip(&self, ip: &str, scope: ApiKeyScope) -> RateDecision {
+        let client = format!("ip:{}", ip);
+        let decision = self.take_token(&client, None, Instant::now());
+        if decision != RateDecision::Allowed {
+            self.record_usage(&client, scope, decision);
+        }
+        decision
+    }
+
+    // Checks the client's daily quota, then takes a token from its bucket for the scope,
+    // and records the request in today's usage.
+    pub fn check(&self, client: &str, scope: ApiKeyScope) -> RateDecision {
+        let decision = match self.quota_exceeded(client) {
+            Some(retry_after) => RateDecision::QuotaExceeded { retry_after },
+            None => self.take_token(client, Some(scope), Instant::now()),
+        };
+        self.record_usage(client, scope, decision);
+        decision
+    }
+
+    // Returns the per-minute limit of a scope, 0 if unlimited.
+    pub fn limit(&self, scope: ApiKeyScope) -> u32 {
+        self.limits.get(&scope).copied().unwrap_or_default()
+    }
+
+    // Returns the per-minute limit of an IP address before authentication, 0 if unlimited.
+    pub fn ip_limit(&self) -> u32 {
+        self.ip_per_minute
+    }
+
+    // Returns the daily quota of a client, 0 if unlimited.
+    pub fn daily_quota(&self) -> u64 {
+        self.daily_quota
+    }
+
+    // Reports the usage of every client on a day (YYYY-MM-DD), today if None.
+    pub fn usage(&self, date: Option<&str>) -> AppResult<UsageReportDto> {
+        let date = match date {
+            Some(date) => parse_date(date)?,
+            None => today_in(self.timezone),
+        };
+
+        let mut clients: Vec<ClientUsageDto> = self
+            .usage
+            .lock()
+            .ok()
+            .and_then(|usage| {
+                usage.get(&date).map(|counters| {
+                    counters
+                        .iter()
+                        .map(|((client, scope), counter)| ClientUsageDto {
+                            client: client.clone(),
+                            scope: scope.as_str().to_string(),
+                            requests: counter.requests,
+                            limited: counter.limited,
+                        })
+                        .collect()
+                })
+            })
+            .unwrap_or_default();
+        clients.sort_by(|a, b| {
+            (b.requests + b.limited)
+                .cmp(&(a.requests + a.limited))
+                .then_with(|| a.client.cmp(&b.client))
+                .then_with(|| a.scope.cmp(&b.scope))
+        });
+
+        Ok(UsageReportDto {
+            date: date.format("%Y-%m-%d").to_string(),
+            clients,
+        })
+    }
+
+    // Returns the per-minute limit of a bucket: the scope's, or the IP limit for the buckets without a scope.
+    fn per_minute(&self, scope: Option<ApiKeyScope>) -> u32 {
+        scope.map_or(self.ip_per_minute, |scope| self.limit(scope))
+    }
+
+    // Returns the time until the next day if the client has used up its daily quota, None otherwise.
+    fn quota_exceeded(&self, client: &str) -> Option<Duration> {
+        if self.daily_quota == 0 {
+            return None;
+        }
+        let usage = self.usage.lock().ok()?;
+        let used: u64 = usage
+            .get(&today_in(self.timezone))?
+            .iter()
+            .filter(|((counted, _), _)| counted == client)
+            .map(|(_, counter)| counter.requests)
+            .sum();
+        (used >= self.daily_quota).then(|| until_next_day(self.timezone))
+    }
+
+    // Refills the client's bucket up to `now` and takes a token if one is available.
+    fn take_token(&self, client: &str, scope: Option<ApiKeyScope>, now: Instant) -> RateDecision {
+        let per_minute = self.per_minute(scope);
+        if per_minute == 0 {
+            return RateDecision::Allowed;
+        }
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return RateDecision::Allowed;
+        };
+
+        let capacity = f64::from(per_minute);
+        let per_second = capacity / 60.0;
+
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            buckets.retain(|(_, scope), bucket| {
+                let capacity = f64::from(self.per_minute(*scope));
+                let refilled = bucket.tokens
+                    + now.duration_since(bucket.refilled_at).as_secs_f64() * capacity / 60.0;
+                refilled < capacity
+            });
+        }
+
+        let bucket = buckets
+            .entry((client.to_string(), scope))
+            .or_insert(TokenBucket {
+                tokens: capacity,
+                refilled_at: now,
+            });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            RateDecision::Allowed
+        } else {
+            RateDecision::Limited {
+                retry_after: Duration::from_secs_f64((1.0 - bucket.tokens) / per_second),
+            }
+        }
+    }
+
+    // Counts the request in today's usage, dropping days past the retention period.
+    fn record_usage(&self, client: &str, scope: ApiKeyScope, decision: RateDecision) {
+        let Ok(mut usage) = self.usage.lock() else {
+            return;
+        };
+
+        let today = today_in(self.timezone);
+        if !usage.contains_key(&today) {
+            let oldest = today - chrono::Duration::days(USAGE_RETENTION_DAYS - 1);
+            usage.retain(|date, _| *date >= oldest);
+        }
+
+        let counters = usage.entry(today).or_default();
+        let key = (client.to_string(), scope);
+        if !counters.contains_key(&key) && counters.len() >= MAX_TRACKED_CLIENTS {
+            fold_least_used(counters);
+        }
+        let counter = counters.entry(key).or_default();
+        match decision {
+            RateDecision::Allowed => counter.requests += 1,
+            RateDecision::Limited { .. } | RateDecision::QuotaExceeded { .. } => {
+                counter.limited += 1
+            }
+        }
+    }
+}
+
+// Folds the least used client counter of a day into the OTHER_CLIENTS counter of its scope, making room for
+// another client.
+fn fold_least_used(counters: &mut DailyUsage) {
+    let least_used = counters
+        .iter()
+        .filter(|((client, _), _)| client != OTHER_CLIENTS)
+        .min_by_key(|(_, counter)| counter.total())
+        .map(|(key, _)| key.clone());
+    let Some(key) = least_used else {
+        return;
+    };
+    let Some(folded) = counters.remove(&key) else {
+        return;
+    };
+
+    let other = counters
+        .entry((OTHER_CLIENTS.to_string(), key.1))
+        .or_default();
+    other.requests += folded.requests;
+    other.limited += folded.limited;
+}
+
+// Returns the time until the next day starts in the time zone, when daily quotas are reset.
+fn until_next_day(timezone: Tz) -> Duration {
+    let now = Utc::now().with_timezone(&timezone);
+    now.date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|tomorrow| tomorrow.and_hms_opt(0, 0, 0))
+        .and_then(|midnight| timezone.from_local_datetime(&midnight).earliest())
+        .and_then(|midnight| (midnight - now).to_std().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(read_per_minute: u32) -> RateLimitUseCase {
+        RateLimitUseCase::new(read_per_minute, 0, 0, 0, 0, Tz::Asia__Jakarta)
+    }
+
+    #[test]
+    fn token_bucket_allows_a_burst_up_to_the_limit() {
+        let limiter = limiter(3);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(
+                limiter.take_token("client", Some(ApiKeyScope::Read), now),
+                RateDecision::Allowed
+            );
+        }
+        assert_eq!(
+            limiter.take_token("client", Some(ApiKeyScope::Read), now),
+            RateDecision::Limited {
+                retry_after: Duration::from_secs(20)
+            }
+        );
+    }
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let limiter = limiter(60);
+        let start = Instant::now();
+        for _ in 0..60 {
+            limiter.take_token("client", Some(ApiKeyScope::Read), start);
+        }
+
+        let later = start + Duration::from_millis(1500);
+        assert_eq!(
+            limiter.take_token("client", Some(ApiKeyScope::Read), later),
+            RateDecision::Allowed
+        );
+        assert!(matches!(
+            limiter.take_token("client", Some(ApiKeyScope::Read), later),
+            RateDecision::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn token_bucket_is_per_client_and_scope() {
+        let limiter = limiter(1);
+        let now = Instant::now();
+
+        assert_eq!(
+            limiter.take_token("a", Some(ApiKeyScope::Read), now),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.take_token("b", Some(ApiKeyScope::Read), now),
+            RateDecision::Allowed
+        );
+        // A limit of 0 disables rate limiting for the scope.
+        for _ in 0..100 {
+            assert_eq!(
+                limiter.take_token("a", Some(ApiKeyScope::Scrape), now),
+                RateDecision::Allowed
+            );
+        }
+    }
+
+    #[test]
+    fn ip_buckets_are_separate_from_the_scope_buckets() {
+        let limiter = RateLimitUseCase::new(1, 0, 0, 2, 0, Tz::Asia__Jakarta);
+
+        assert_eq!(
+            limiter.check_ip("1.2.3.4", ApiKeyScope::Read),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check("ip:1.2.3.4", ApiKeyScope::Read),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check_ip("1.2.3.4", ApiKeyScope::Read),
+            RateDecision::Allowed
+        );
+        assert!(matches!(
+            limiter.check_ip("1.2.3.4", ApiKeyScope::Read),
+            RateDecision::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn daily_quota_counts_requests_across_scopes() {
+        let limiter = RateLimitUseCase::new(0, 0, 0, 0, 2, Tz::Asia__Jakarta);
+
+        assert_eq!(
+            limiter.check("key:a", ApiKeyScope::Read),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check("key:a", ApiKeyScope::Scrape),
+            RateDecision::Allowed
+        );
+        let decision = limiter.check("key:a", ApiKeyScope::Read);
+        let RateDecision::QuotaExceeded { retry_after } = decision else {
+            panic!("expected the quota to be exceeded, got {:?}", decision);
+        };
+        assert!(retry_after <= Duration::from_secs(24 * 3600));
+        // Other clients have their own quota.
+        assert_eq!(
+            limiter.check("key:b", ApiKeyScope::Read),
+            RateDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn usage_folds_the_least_used_clients_beyond_the_cap() {
+        let limiter = limiter(0);
+        for _ in 0..3 {
+            limiter.record_usage("heavy", ApiKeyScope::Read, RateDecision::Allowed);
+        }
+        for i in 0..MAX_TRACKED_CLIENTS + 10 {
+            let client = format!("ip:{}", i);
+            limiter.record_usage(&client, ApiKeyScope::Read, RateDecision::Allowed);
+        }
+
+        let report = limiter.usage(None).unwrap();
+
+        assert_eq!(report.clients.len(), MAX_TRACKED_CLIENTS + 1);
+        assert_eq!(report.clients[0].client, OTHER_CLIENTS);
+        assert_eq!(report.clients[0].requests, 11);
+        assert_eq!(report.clients[1].client, "heavy");
+        assert_eq!(report.clients[1].requests, 3);
+        let total: u64 = report.clients.iter().map(|client| client.requests).sum();
+        assert_eq!(total, 3 + MAX_TRACKED_CLIENTS as u64 + 10);
+    }
+}
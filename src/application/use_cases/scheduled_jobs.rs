@@ -0,0 +1,482 @@
+use crate::application::dto::job_run_dto::{
+    JobRunDto, QueuedJobRunDto, ScheduledJobStatusDto, SchedulerStatusDto,
+};
+use crate::application::use_cases::scrape_holidays::ScrapeHolidaysUseCase;
+use crate::domain::entities::scheduled_job::{
+    JobRun, JobRunStatus, ScheduledJob, ScheduledJobKind, PREFETCH_FROM_MONTH,
+};
+use crate::domain::repositories::holiday_repository::HolidayRepository;
+use crate::domain::repositories::job_run_repository::JobRunRepository;
+use crate::domain::services::holiday_service::HolidayDomainService;
+use crate::domain::services::leader_election::{LeaderElection, LeadershipChange};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use chrono::{DateTime, Datelike, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+use uuid::Uuid;
+
+// TriggeredRun is a run of a job requested over the API, waiting in the queue of the scheduler.
+// It contains:
+// - id: The id the run is recorded under once it has run.
+// - job: The job to run.
+// - requested_at: When the run was requested, recorded as the time the run was due.
+struct TriggeredRun {
+    id: String,
+    job: ScheduledJob,
+    requested_at: NaiveDateTime,
+}
+
+// ScheduledJobsUseCase runs the jobs of the scheduler and records every run in the run history.
+// The scheduler decides when a job is due; this use case decides what a run of each job kind does.
+// Schedules and run times are in the service time zone, so e.g. New Year starts at midnight WIB on any host.
+// When several replicas run, only the elected leader runs the jobs, on their schedules or when requested over the API.
+// Runs of the same job never overlap.
+pub struct ScheduledJobsUseCase {
+    jobs: Vec<ScheduledJob>,
+    timezone: Tz,
+    enabled: bool,
+    scrape_use_case: Arc<ScrapeHolidaysUseCase>,
+    holiday_repository: Arc<dyn HolidayRepository>,
+    run_repository: Arc<dyn JobRunRepository>,
+    leader_election: Arc<dyn LeaderElection>,
+    is_leader: AtomicBool,
+    holiday_service: HolidayDomainService,
+    running: HashMap<ScheduledJobKind, Mutex<()>>,
+    triggered: Mutex<VecDeque<TriggeredRun>>,
+    trigger_available: Notify,
+}
+
+impl ScheduledJobsUseCase {
+    // Constructor to initialize ScheduledJobsUseCase with the configured jobs and their time zone, whether the scheduler
+    // runs them, the scrape use case, the holiday repository (for validation), the repository of the run history
+    // and the leader election deciding which replica runs them.
+    pub fn new(
+        jobs: Vec<ScheduledJob>,
+        timezone: Tz,
+        enabled: bool,
+        scrape_use_case: Arc<ScrapeHolidaysUseCase>,
+        holiday_repository: Arc<dyn HolidayRepository>,
+        run_repository: Arc<dyn JobRunRepository>,
+        leader_election: Arc<dyn LeaderElection>,
+    ) -> Self {
+        let running = jobs.iter().map(|job| (job.kind, Mutex::new(()))).collect();
+        Self {
+            jobs,
+            timezone,
+            enabled,
+            scrape_use_case,
+            holiday_repository,
+            run_repository,
+            leader_election,
+            is_leader: AtomicBool::new(false),
+            holiday_service: HolidayDomainService::new(),
+            running,
+            triggered: Mutex::new(VecDeque::new()),
+            trigger_available: Notify::new(),
+        }
+    }
+
+    // Parses the scheduled jobs from a semicolon-separated list of `job=cron` entries,
+    // e.g. "scrape-current-year=1 0 1 1 *;validate-data=30 2 * * *".
+    // An empty list schedules every job kind with its default cron expression.
+    // Returns a Configuration error if the list is invalid.
+    pub fn parse_jobs(spec: &str) -> AppResult<Vec<ScheduledJob>> {
+        if spec.trim().is_empty() {
+            return ScheduledJobKind::ALL
+                .into_iter()
+                .map(|kind| ScheduledJob::new(kind, kind.default_schedule()))
+                .collect();
+        }
+
+        let mut jobs: Vec<ScheduledJob> = Vec::new();
+        for entry in spec.split(';').filter(|entry| !entry.trim().is_empty()) {
+            let Some((name, schedule)) = entry.split_once('=') else {
+                return Err(AppError::Configuration(format!(
+                    "Invalid scheduled job '{}', expected job=cron",
+                    entry.trim()
+                )));
+            };
+
+            let kind = ScheduledJobKind::parse(name).ok_or_else(|| {
+                let expected: Vec<&str> =
+                    ScheduledJobKind::ALL.iter().map(|k| k.as_str()).collect();
+                AppError::Configuration(format!(
+                    "Invalid scheduled job '{}', expected one of: {}",
+                    name.trim(),
+                    expected.join(", ")
+                ))
+            })?;
+            if jobs.iter().any(|job| job.kind == kind) {
+                return Err(AppError::Configuration(format!(
+                    "Scheduled job {} is configured more than once",
+                    kind.as_str()
+                )));
+            }
+
+            jobs.push(ScheduledJob::new(kind, schedule)?);
+        }
+
+        Ok(jobs)
+    }
+
+    // Returns the scheduled jobs.
+    pub fn jobs(&self) -> &[ScheduledJob] {
+        &self.jobs
+    }
+
+    // Returns the current time in the time zone of the schedules.
+    pub fn now(&self) -> DateTime<Tz> {
+        Utc::now().with_timezone(&self.timezone)
+    }
+
+    // Runs a leader election round and reports how this instance's leadership changed.
+    // If the election fails, e.g. because the database is unreachable, this instance stops being the leader,
+    // since it can no longer be sure that no other instance is.
+    pub async fn refresh_leadership(&self) -> AppResult<LeadershipChange> {
+        let acquired = self.leader_election.try_acquire().await;
+        let is_leader = *acquired.as_ref().unwrap_or(&false);
+        let was_leader = self.is_leader.swap(is_leader, Ordering::SeqCst);
+
+        acquired.map(|_| LeadershipChange::from_transition(was_leader, is_leader))
+    }
+
+    // Gives up leadership, e.g. on shutdown, so another instance can take over right away.
+    pub async fn resign(&self) -> AppResult<()> {
+        self.is_leader.store(false, Ordering::SeqCst);
+        self.leader_election.release().await
+    }
+
+    // Whether this instance is the leader, which runs the jobs on their schedules.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::SeqCst)
+    }
+
+    // Confirms that this instance is still the leader by renewing the lock, rather than trusting the outcome of
+    // the last election round, which may be up to a check interval old.
+    // Returns a ServiceUnavailable error if the scheduler is disabled or another instance is (or may be) the leader.
+    // A follower is never promoted here; taking over is left to the scheduler's election rounds.
+    pub async fn confirm_leadership(&self) -> AppResult<()> {
+        if !self.enabled {
+            return Err(AppError::ServiceUnavailable(
+                "The scheduler is disabled on this instance (SCHEDULER_ENABLED=false)".to_string(),
+            ));
+        }
+        if !self.is_leader() {
+            return Err(AppError::ServiceUnavailable(
+                "This instance is not the scheduler leader".to_string(),
+            ));
+        }
+
+        match self.refresh_leadership().await? {
+            LeadershipChange::Kept | LeadershipChange::Acquired => Ok(()),
+            LeadershipChange::Lost | LeadershipChange::Follower => {
+                println!("⚠️  This instance is no longer the scheduler leader");
+                Err(AppError::ServiceUnavailable(
+                    "This instance is no longer the scheduler leader".to_string(),
+                ))
+            }
+        }
+    }
+
+    // Reports every scheduled job with its next run and its last run.
+    pub async fn status(&self) -> AppResult<SchedulerStatusDto> {
+        let now = self.now();
+        let mut jobs = Vec::new();
+        for job in &self.jobs {
+            let last_run = self
+                .run_repository
+                .list_runs(Some(job.name()), 1)
+                .await?
+                .into_iter()
+                .next()
+                .map(JobRunDto::from);
+            let next_run = job
+                .next_run_after(&now)
+                .filter(|_| self.enabled)
+                .map(|next| next.format("%Y-%m-%d %H:%M:%S").to_string());
+
+            jobs.push(ScheduledJobStatusDto {
+                job: job.name().to_string(),
+                schedule: job.schedule.clone(),
+                next_run,
+                last_run,
+            });
+        }
+
+        Ok(SchedulerStatusDto {
+            enabled: self.enabled,
+            leader: self.is_leader(),
+            timezone: self.timezone.name().to_string(),
+            jobs,
+        })
+    }
+
+    // Queues a run of a scheduled job, e.g. when triggered by an admin, for the scheduler of this instance to run
+    // as soon as no other run of the job is in progress. Returns the queued run without waiting for it; once it
+    // has run, it is recorded in the run history under the same id. If a run of the job is already queued,
+    // that run is returned instead of queueing another one.
+    // Returns a NotFound error if no job with that name is scheduled, and a ServiceUnavailable error unless this
+    // instance is the scheduler leader, so a job never runs on two replicas at once.
+    pub async fn trigger_job(&self, name: &str) -> AppResult<QueuedJobRunDto> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|job| job.name() == name.trim().to_lowercase())
+            .ok_or_else(|| {
+                let scheduled: Vec<&str> = self.jobs.iter().map(|job| job.name()).collect();
+                AppError::NotFound(format!(
+                    "Scheduled job '{}' not found, expected one of: {}",
+                    name,
+                    scheduled.join(", ")
+                ))
+            })?;
+
+        self.confirm_leadership().await?;
+
+        let mut triggered = self.triggered.lock().await;
+        if let Some(queued) = triggered.iter().find(|run| run.job.kind == job.kind) {
+            return Ok(QueuedJobRunDto::new(
+                queued.id.clone(),
+                job.name(),
+                queued.requested_at,
+            ));
+        }
+
+        let run = TriggeredRun {
+            id: Uuid::new_v4().to_string(),
+            job: job.clone(),
+            requested_at: self.now().naive_local(),
+        };
+        let queued = QueuedJobRunDto::new(run.id.clone(), job.name(), run.requested_at);
+        triggered.push_back(run);
+        self.trigger_available.notify_one();
+
+        println!("📅 Queued a run of job {} on request", job.name());
+        Ok(queued)
+    }
+
+    // Runs the oldest queued run of a job, if any. Returns whether there was one.
+    // A run is only started while this instance is still the leader; otherwise it is dropped.
+    // Only failing to record the run is an error.
+    pub async fn run_next_triggered(&self) -> AppResult<bool> {
+        let Some(run) = self.triggered.lock().await.pop_front() else {
+            return Ok(false);
+        };
+        if let Err(e) = self.confirm_leadership().await {
+            println!(
+                "⏭️  Dropping the requested run of job {}: {}",
+                run.job.name(),
+                e
+            );
+            return Ok(true);
+        }
+
+        println!("📅 Running job {} on request", run.job.name());
+        let recorded = self.record_run(run.id, &run.job, run.requested_at).await?;
+        println!(
+            "🏁 Requested run of job {} {}: {}",
+            run.job.name(),
+            recorded.status,
+            recorded.message
+        );
+        Ok(true)
+    }
+
+    // Waits until a run of a job is queued.
+    pub async fn wait_for_triggered(&self) {
+        self.trigger_available.notified().await
+    }
+
+    // Drops the requested runs that have not started, e.g. on shutdown, and returns how many there were.
+    pub async fn discard_triggered(&self) -> usize {
+        let mut triggered = self.triggered.lock().await;
+        let count = triggered.len();
+        triggered.clear();
+        count
+    }
+
+    // Runs a job that was due at `scheduled_for` and records the run in the run history.
+    // A failing job is recorded as failed rather than returned as an error; only failing to record the run is an error.
+    pub async fn run_job(
+        &self,
+        job: &ScheduledJob,
+        scheduled_for: NaiveDateTime,
+    ) -> AppResult<JobRunDto> {
+        self.record_run(Uuid::new_v4().to_string(), job, scheduled_for)
+            .await
+    }
+
+    // Runs a job and records the run under the given id. Waits for a run of the same job that is in progress,
+    // so e.g. a requested scrape never runs alongside the scheduled one.
+    async fn record_run(
+        &self,
+        id: String,
+        job: &ScheduledJob,
+        scheduled_for: NaiveDateTime,
+    ) -> AppResult<JobRunDto> {
+        let _running = match self.running.get(&job.kind) {
+            Some(running) => Some(running.lock().await),
+            None => None,
+        };
+
+        let started_at = self.now().naive_local();
+        let (status, message) = match self.execute(job.kind, scheduled_for).await {
+            Ok(outcome) => outcome,
+            Err(e) => (JobRunStatus::Failed, e.to_string()),
+        };
+
+        let run = JobRun {
+            id,
+            job: job.name().to_string(),
+            scheduled_for,
+            started_at,
+            finished_at: self.now().naive_local(),
+            status,
+            message,
+        };
+        self.run_repository.save_run(run.clone()).await?;
+
+        Ok(JobRunDto::from(run))
+    }
+
+    // Runs the job if it missed a run while the server was down, e.g. the January 1st scrape.
+    // A run was missed if the job was due after its last successful run; only the latest missed run is executed,
+    // since every job kind works on the current state rather than on the period it was due for.
+    // A job that never succeeded has nothing to catch up from; missing data is bootstrapped separately on startup.
+    pub async fn catch_up(
+        &self,
+        job: &ScheduledJob,
+        now: DateTime<Tz>,
+    ) -> AppResult<Option<JobRunDto>> {
+        let Some(last_success) = self.run_repository.last_successful_run(job.name()).await? else {
+            return Ok(None);
+        };
+        let Some(last_due) = self
+            .timezone
+            .from_local_datetime(&last_success.scheduled_for)
+            .earliest()
+        else {
+            return Ok(None);
+        };
+
+        match job.latest_run_between(&last_due, &now) {
+            Some(missed) => {
+                println!(
+                    "🔁 Job {} missed its run at {}, catching up",
+                    job.name(),
+                    missed.format("%Y-%m-%d %H:%M:%S")
+                );
+                self.run_job(job, missed.naive_local()).await.map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Lists the most recent runs, newest first, optionally only those of one job.
+    pub async fn list_runs(&self, job: Option<&str>, limit: usize) -> AppResult<Vec<JobRunDto>> {
+        let runs = self.run_repository.list_runs(job, limit).await?;
+        Ok(runs.into_iter().map(JobRunDto::from).collect())
+    }
+
+    // Does the work of a job kind. The current year is the year the run was due in.
+    async fn execute(
+        &self,
+        kind: ScheduledJobKind,
+        scheduled_for: NaiveDateTime,
+    ) -> AppResult<(JobRunStatus, String)> {
+        let year = scheduled_for.year();
+
+        match kind {
+            ScheduledJobKind::ScrapeCurrentYear => {
+                let message = self.scrape_year(year).await?;
+                Ok((JobRunStatus::Succeeded, message))
+            }
+            ScheduledJobKind::PrefetchNextYear => {
+                if scheduled_for.month() < PREFETCH_FROM_MONTH {
+                    return Ok((
+                        JobRunStatus::Skipped,
+                        format!(
+                            "Year {} is only pre-fetched from October, during Q4",
+                            year + 1
+                        ),
+                    ));
+                }
+                let message = self.scrape_year(year + 1).await?;
+                Ok((JobRunStatus::Succeeded, message))
+            }
+            ScheduledJobKind::Rescrape => {
+                let mut years = vec![year];
+                if self
+                    .holiday_repository
+                    .holidays_exist_for_year(year + 1)
+                    .await?
+                {
+                    years.push(year + 1);
+                }
+
+                // Every year is scraped, even if an earlier one failed
+                let mut messages = Vec::new();
+                let mut failed = false;
+                for year in years {
+                    match self.scrape_year(year).await {
+                        Ok(message) => messages.push(message),
+                        Err(e) => {
+                            failed = true;
+                            messages.push(format!("Year {}: {}", year, e));
+                        }
+                    }
+                }
+
+                let status = if failed {
+                    JobRunStatus::Failed
+                } else {
+                    JobRunStatus::Succeeded
+                };
+                Ok((status, messages.join("; ")))
+            }
+            ScheduledJobKind::ValidateData => {
+                let years = self.holiday_repository.list_available_years().await?;
+                let mut issues = Vec::new();
+                for year in &years {
+                    let holidays = self.holiday_repository.get_holidays_by_year(*year).await?;
+                    issues.extend(self.holiday_service.validate_holidays(*year, &holidays));
+                }
+
+                if issues.is_empty() {
+                    Ok((
+                        JobRunStatus::Succeeded,
+                        format!("Validated {} years, no issues found", years.len()),
+                    ))
+                } else {
+                    Ok((
+                        JobRunStatus::Failed,
+                        format!(
+                            "Validated {} years, found {} issues: {}",
+                            years.len(),
+                            issues.len(),
+                            issues.join("; ")
+                        ),
+                    ))
+                }
+            }
+        }
+    }
+
+    // Scrapes a year with the default merge strategy and describes the outcome.
+    async fn scrape_year(&self, year: i32) -> AppResult<String> {
+        let strategy = self.scrape_use_case.default_strategy();
+        let outcome = self.scrape_use_case.scrape(year, strategy).await?;
+
+        let mut message = format!("Scraped {} holidays for {}", outcome.holidays.len(), year);
+        if !outcome.conflicts.is_empty() {
+            message.push_str(&format!(
+                " ({} conflicts with manual entries)",
+                outcome.conflicts.len()
+            ));
+        }
+        Ok(message)
+    }
+}
@@ -1,49 +1,248 @@
-use crate::application::dto::holiday_dto::HolidayDto;
+use crate::domain::entities::holiday_merge::{MergeOutcome, MergeStrategy};
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
 use crate::domain::repositories::holiday_repository::HolidayRepository;
-use crate::domain::services::holiday_service::HolidayScrapingService;
-use crate::shared::errors::app_error::AppResult;
-use std::sync::Arc;
+use crate::domain::services::holiday_service::{HolidayDomainService, HolidayScrapingService};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{oneshot, OnceCell};
+
+// The in-flight scrape of a year: the strategy it runs with and its result, shared by every caller that joins it.
+#[derive(Clone)]
+struct InFlightScrape {
+    strategy: MergeStrategy,
+    result: Arc<OnceCell<AppResult<MergeOutcome>>>,
+}
 
 // The ScrapeHolidaysUseCase struct orchestrates the process of scraping holidays and saving them to the repository.
+// The scraped holidays are merged into the stored ones following a MergeStrategy; manual corrections stay in the
+// override repository, and a scrape never withdraws them.
+// Scrapes of the same year are serialised: concurrent callers share the running scrape and its result, and a caller
+// asking for another strategy waits for it to finish before starting its own.
 pub struct ScrapeHolidaysUseCase {
     holiday_repository: Arc<dyn HolidayRepository>,
+    override_repository: Arc<dyn HolidayOverrideRepository>,
     scraping_service: Arc<dyn HolidayScrapingService>,
+    holiday_service: HolidayDomainService,
+    default_strategy: MergeStrategy,
+    in_flight: Mutex<HashMap<i32, InFlightScrape>>,
 }
 
 impl ScrapeHolidaysUseCase {
-    // Constructor to initialize ScrapeHolidaysUseCase with dependencies: repository and scraping service.
+    // Constructor to initialize ScrapeHolidaysUseCase with dependencies: the holiday and override repositories,
+    // the scraping service, and the merge strategy used when a caller does not ask for one.
     pub fn new(
         holiday_repository: Arc<dyn HolidayRepository>,
+        override_repository: Arc<dyn HolidayOverrideRepository>,
         scraping_service: Arc<dyn HolidayScrapingService>,
+        default_strategy: MergeStrategy,
     ) -> Self {
         Self {
             holiday_repository,
+            override_repository,
             scraping_service,
+            holiday_service: HolidayDomainService::new(),
+            default_strategy,
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Parses the name of a merge strategy, e.g. from the `strategy` query parameter.
+    // Returns None if no strategy was given, so the default strategy applies.
+    pub fn parse_strategy(strategy: Option<&str>) -> AppResult<Option<MergeStrategy>> {
+        let Some(strategy) = strategy.filter(|s| !s.trim().is_empty()) else {
+            return Ok(None);
+        };
+
+        MergeStrategy::parse(strategy).map(Some).ok_or_else(|| {
+            let expected: Vec<&str> = MergeStrategy::ALL.iter().map(|s| s.as_str()).collect();
+            AppError::BadRequest(format!(
+                "Invalid merge strategy '{}', expected one of: {}",
+                strategy,
+                expected.join(", ")
+            ))
+        })
+    }
+
+    // Scrapes the holidays of a year and merges them into the repository, returning the merge outcome.
+    // If a scrape of the year is already running with the same strategy, this call waits for it and returns its
+    // result instead of starting another fetch and write. If it runs with another strategy, this call waits for it
+    // to finish and then scrapes again with its own strategy.
+    pub async fn scrape(&self, year: i32, strategy: MergeStrategy) -> AppResult<MergeOutcome> {
+        loop {
+            let scrape = self.join_or_start(year, strategy);
+
+            // Only one caller runs the scrape; the others wait for the shared result.
+            // If the running caller is cancelled, one of the waiting callers takes over with the scrape's strategy.
+            let result = scrape
+                .result
+                .get_or_init(|| self.scrape_and_save(year, scrape.strategy))
+                .await
+                .clone();
+
+            // The finished scrape is forgotten, so the next request for this year scrapes again.
+            if let Ok(mut in_flight) = self.in_flight.lock() {
+                if in_flight
+                    .get(&year)
+                    .is_some_and(|current| Arc::ptr_eq(&current.result, &scrape.result))
+                {
+                    in_flight.remove(&year);
+                }
+            }
+
+            if scrape.strategy == strategy {
+                return result;
+            }
+        }
+    }
+
+    // Returns the merge strategy used when a caller does not ask for one.
+    pub fn default_strategy(&self) -> MergeStrategy {
+        self.default_strategy
+    }
+
+    // Returns the in-flight scrape of the year, registering a new one with the strategy if none is running.
+    fn join_or_start(&self, year: i32, strategy: MergeStrategy) -> InFlightScrape {
+        let scrape = InFlightScrape {
+            strategy,
+            result: Arc::new(OnceCell::new()),
+        };
+        let Ok(mut in_flight) = self.in_flight.lock() else {
+            return scrape;
+        };
+
+        if let Some(running) = in_flight.get(&year) {
+            if running.strategy == strategy {
+                println!("Joining in-flight scrape for year {}", year);
+            } else {
+                println!(
+                    "Waiting for the {} scrape of year {} before scraping with {}",
+                    running.strategy.as_str(),
+                    year,
+                    strategy.as_str()
+                );
+            }
+            return running.clone();
         }
+
+        in_flight.insert(year, scrape.clone());
+        scrape
     }
 
-    // The main method to execute the use case: scrape holidays and save them to the repository.
-    // It also converts the results into DTOs before returning them.
-    pub async fn execute(&self, year: i32) -> AppResult<Vec<HolidayDto>> {
-        println!("Starting to scrape holidays for year {}", year);
+    // Scrapes the holidays of a year, merges them into the stored holidays and saves the result.
+    async fn scrape_and_save(&self, year: i32, strategy: MergeStrategy) -> AppResult<MergeOutcome> {
+        println!(
+            "Starting to scrape holidays for year {} ({})",
+            year,
+            strategy.as_str()
+        );
 
-        // Step 1: Scrape holidays using the scraping service.
-        let holidays = self.scraping_service.scrape_holidays_for_year(year).await?;
+        // Step 1: Scrape holidays using the scraping service, and read the manual corrections of the year.
+        let scraped = self.scraping_service.scrape_holidays_for_year(year).await?;
+        let overrides = self.override_repository.list_overrides(year).await?;
 
-        // Step 2: Save the scraped holidays to the holiday repository.
+        // Step 2: Merge them into the stored holidays and save the result as one update,
+        // so no other write to the year can land between reading and saving it.
+        // A year without data starts empty.
+        let (outcome_tx, outcome_rx) = oneshot::channel();
+        let holiday_service = self.holiday_service.clone();
         self.holiday_repository
-            .save_holidays(holidays.clone(), year) // Pass a clone of the holidays since it's being used again
+            .update_year(
+                year,
+                Box::new(move |existing| {
+                    let outcome =
+                        holiday_service.merge_scraped(existing, scraped, &overrides, strategy);
+                    let holidays = outcome.holidays.clone();
+                    let _ = outcome_tx.send(outcome);
+                    holidays
+                }),
+            )
             .await?;
+        let outcome = outcome_rx.await.map_err(|_| {
+            AppError::InternalServer(format!("Merge of year {} did not complete", year))
+        })?;
+        for conflict in &outcome.conflicts {
+            println!(
+                "Merge conflict on {}: scraped '{}' vs. manual {:?} ({})",
+                conflict.date, conflict.scraped, conflict.manual, conflict.resolution
+            );
+        }
 
         println!(
-            "Successfully scraped and saved {} holidays for year {}",
-            holidays.len(),
-            year
+            "Successfully scraped and saved {} holidays for year {} ({} conflicts)",
+            outcome.holidays.len(),
+            year,
+            outcome.conflicts.len()
         );
 
-        // Step 3: Convert the domain holidays into DTOs and return them.
-        let holiday_dtos: Vec<HolidayDto> = holidays.into_iter().map(HolidayDto::from).collect();
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::holiday::Holiday;
+    use crate::infrastructure::persistence::file_override_repository::FileHolidayOverrideRepository;
+    use crate::infrastructure::persistence::file_repository::FileHolidayRepository;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    // Scrapes slowly and records how many scrapes ran at the same time.
+    #[derive(Default)]
+    struct SlowScraper {
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HolidayScrapingService for SlowScraper {
+        async fn scrape_holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            let date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+            Ok(vec![Holiday::new(date, "Tahun Baru".to_string(), false)])
+        }
+    }
+
+    #[tokio::test]
+    async fn scrapes_of_a_year_are_serialised_across_strategies() {
+        let data_dir = std::env::temp_dir().join(format!("scrapes-{}", uuid::Uuid::new_v4()));
+        let data_dir = data_dir.to_string_lossy().into_owned();
+        let scraper = Arc::new(SlowScraper::default());
+        let use_case = Arc::new(ScrapeHolidaysUseCase::new(
+            Arc::new(FileHolidayRepository::new(data_dir.clone())),
+            Arc::new(FileHolidayOverrideRepository::new(data_dir.clone())),
+            scraper.clone(),
+            MergeStrategy::MergeKeepManual,
+        ));
+
+        let mut tasks = Vec::new();
+        for strategy in [
+            MergeStrategy::MergeKeepManual,
+            MergeStrategy::AddOnly,
+            MergeStrategy::MergeKeepManual,
+            MergeStrategy::Replace,
+        ] {
+            let use_case = use_case.clone();
+            tasks.push(tokio::spawn(async move {
+                use_case.scrape(2025, strategy).await
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        let _ = std::fs::remove_dir_all(&data_dir);
 
-        Ok(holiday_dtos)
+        // Callers with the same strategy may share a scrape, but scrapes never overlap.
+        assert_eq!(scraper.max_running.load(Ordering::SeqCst), 1);
+        assert!(scraper.calls.load(Ordering::SeqCst) >= 3);
     }
 }
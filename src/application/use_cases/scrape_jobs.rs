@@ -0,0 +1,134 @@
+use crate::application::dto::scrape_job_dto::ScrapeJobDto;
+use crate::application::use_cases::scrape_holidays::ScrapeHolidaysUseCase;
+use crate::domain::entities::holiday_merge::MergeStrategy;
+use crate::domain::entities::scrape_job::ScrapeJob;
+use crate::domain::repositories::scrape_job_repository::ScrapeJobRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::{is_valid_year, now_in};
+use chrono_tz::Tz;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+// ScrapeJobsUseCase runs scrapes as background jobs.
+// Submitting a job only persists it as queued; a background worker picks up queued jobs one at a time
+// and runs ScrapeHolidaysUseCase. Because the queue lives in the job repository, it survives restarts,
+// and replicas sharing the storage share the queue; each job is claimed by one instance, recorded as its owner.
+pub struct ScrapeJobsUseCase {
+    job_repository: Arc<dyn ScrapeJobRepository>,
+    scrape_use_case: Arc<ScrapeHolidaysUseCase>,
+    timezone: Tz,
+    instance_id: String,
+    work_available: Notify,
+}
+
+impl ScrapeJobsUseCase {
+    // Constructor to initialize ScrapeJobsUseCase with the job repository, the scrape use case, the service
+    // time zone, in which the jobs' timestamps are recorded, and the id of this instance (INSTANCE_ID).
+    pub fn new(
+        job_repository: Arc<dyn ScrapeJobRepository>,
+        scrape_use_case: Arc<ScrapeHolidaysUseCase>,
+        timezone: Tz,
+        instance_id: String,
+    ) -> Self {
+        Self {
+            job_repository,
+            scrape_use_case,
+            timezone,
+            instance_id,
+            work_available: Notify::new(),
+        }
+    }
+
+    // Queues a scrape job for the given year and merge strategy, and wakes up the worker.
+    // The Replace strategy is only accepted if `allow_replace` is set, i.e. the client has the admin scope.
+    // Returns the queued job immediately, without waiting for the scrape.
+    pub async fn submit(
+        &self,
+        year: i32,
+        strategy: Option<&str>,
+        allow_replace: bool,
+    ) -> AppResult<ScrapeJobDto> {
+        if !is_valid_year(year) {
+            return Err(AppError::BadRequest(format!(
+                "Year must be between 1900 and 2100, got {}",
+                year
+            )));
+        }
+
+        let strategy = ScrapeHolidaysUseCase::parse_strategy(strategy)?;
+        if strategy == Some(MergeStrategy::Replace) && !allow_replace {
+            return Err(AppError::Forbidden(
+                "The replace strategy requires an API key with the admin scope".to_string(),
+            ));
+        }
+
+        let job = ScrapeJob::new(
+            Uuid::new_v4().to_string(),
+            year,
+            strategy,
+            now_in(self.timezone),
+        );
+        self.job_repository.save_job(job.clone()).await?;
+        self.work_available.notify_one();
+
+        println!("Queued scrape job {} for year {}", job.id, year);
+        Ok(ScrapeJobDto::from(job))
+    }
+
+    // Returns the current state of a job, or a NotFound error if the id is unknown.
+    pub async fn get_job(&self, id: &str) -> AppResult<ScrapeJobDto> {
+        self.job_repository
+            .get_job(id)
+            .await?
+            .map(ScrapeJobDto::from)
+            .ok_or_else(|| AppError::NotFound(format!("Scrape job {} not found", id)))
+    }
+
+    // Puts the jobs this instance was running when it stopped back in the queue.
+    // Jobs running on other instances are left alone.
+    // Returns the number of jobs that were requeued.
+    pub async fn recover_interrupted_jobs(&self) -> AppResult<usize> {
+        self.job_repository.requeue_jobs_of(&self.instance_id).await
+    }
+
+    // Runs the oldest queued job, if any.
+    // Returns `true` if a job was run, `false` if the queue is empty.
+    pub async fn run_next_job(&self) -> AppResult<bool> {
+        let Some(mut job) = self
+            .job_repository
+            .claim_next_job(&self.instance_id, now_in(self.timezone))
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        println!("Running scrape job {} for year {}", job.id, job.year);
+
+        let strategy = job
+            .strategy
+            .unwrap_or_else(|| self.scrape_use_case.default_strategy());
+        match self.scrape_use_case.scrape(job.year, strategy).await {
+            Ok(outcome) => job.succeed(
+                outcome.holidays.len(),
+                outcome.conflicts,
+                now_in(self.timezone),
+            ),
+            Err(e) => job.fail(e.to_string(), now_in(self.timezone)),
+        }
+        println!(
+            "Scrape job {} for year {} {}",
+            job.id,
+            job.year,
+            job.status.as_str()
+        );
+        self.job_repository.save_job(job).await?;
+
+        Ok(true)
+    }
+
+    // Waits until a new job has been submitted on this instance.
+    pub async fn wait_for_jobs(&self) {
+        self.work_available.notified().await;
+    }
+}
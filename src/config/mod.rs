@@ -5,19 +5,173 @@ pub struct Config {
     pub host: String,
     pub port: String,
     pub data_dir: String,
+    pub storage: String,
+    pub sqlite_path: String,
+    pub database_url: Option<String>,
+    pub database_max_connections: u32,
+    pub http_connect_timeout_secs: u64,
+    pub http_read_timeout_secs: u64,
+    pub http_max_retries: u32,
+    pub http_retry_backoff_ms: u64,
+    pub http_user_agent: String,
+    pub http_proxy: Option<String>,
+    pub circuit_breaker_failure_threshold: u32,
+    pub circuit_breaker_reset_timeout_secs: u64,
+    pub crawl_respect_robots: bool,
+    pub crawl_min_interval_ms: u64,
+    pub crawl_cache_ttl_secs: u64,
+    pub backfill_concurrency: usize,
+    pub cache_enabled: bool,
+    pub admin_token: Option<String>,
+    pub api_keys: Option<String>,
+    pub api_key_cache_ttl_secs: u64,
+    pub public_scopes: String,
+    pub rate_limit_read_per_minute: u32,
+    pub rate_limit_scrape_per_minute: u32,
+    pub rate_limit_admin_per_minute: u32,
+    pub rate_limit_ip_per_minute: u32,
+    pub rate_limit_daily_quota: u64,
+    pub trusted_proxies: String,
+    pub scrape_merge_strategy: String,
+    pub timezone: String,
+    pub scheduler_enabled: bool,
+    pub scheduler_jobs: String,
+    pub shutdown_timeout_secs: u64,
+    pub instance_id: String,
 }
 
 impl Config {
     // The `from_env()` function reads environment variables to create a new `Config` instance.
     // If an environment variable is not found, it falls back to a default value.
     pub fn from_env() -> Self {
+        // Reads the "DATA_DIR" environment variable or defaults to "data" if not found.
+        let data_dir = env::var("DATA_DIR").unwrap_or_else(|_| "data".to_string());
+
         Self {
             // Reads the "HOST" environment variable or defaults to "127.0.0.1" if not found.
             host: env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
             // Reads the "PORT" environment variable or defaults to "8080" if not found.
             port: env::var("PORT").unwrap_or_else(|_| "8080".to_string()),
-            // Reads the "DATA_DIR" environment variable or defaults to "data" if not found.
-            data_dir: env::var("DATA_DIR").unwrap_or_else(|_| "data".to_string()),
+            // Reads the "SQLITE_PATH" environment variable or defaults to "holidays.db" in the data directory.
+            sqlite_path: env::var("SQLITE_PATH")
+                .unwrap_or_else(|_| format!("{}/holidays.db", data_dir)),
+            data_dir,
+            // Reads the "DATABASE_URL" environment variable, required when STORAGE is "postgres".
+            database_url: env::var("DATABASE_URL").ok().filter(|v| !v.is_empty()),
+            // Reads the "DATABASE_MAX_CONNECTIONS" environment variable or defaults to 10 pooled connections.
+            database_max_connections: parse_env("DATABASE_MAX_CONNECTIONS", 10),
+            // Reads the "STORAGE" environment variable ("file", "sqlite" or "postgres") or defaults to "file".
+            storage: env::var("STORAGE")
+                .map(|v| v.to_lowercase())
+                .unwrap_or_else(|_| "file".to_string()),
+            // Reads the "HTTP_CONNECT_TIMEOUT_SECS" environment variable or defaults to 10 seconds.
+            http_connect_timeout_secs: parse_env("HTTP_CONNECT_TIMEOUT_SECS", 10),
+            // Reads the "HTTP_READ_TIMEOUT_SECS" environment variable or defaults to 30 seconds.
+            http_read_timeout_secs: parse_env("HTTP_READ_TIMEOUT_SECS", 30),
+            // Reads the "HTTP_MAX_RETRIES" environment variable or defaults to 3 retries.
+            http_max_retries: parse_env("HTTP_MAX_RETRIES", 3),
+            // Reads the "HTTP_RETRY_BACKOFF_MS" environment variable or defaults to 500 milliseconds.
+            // The delay doubles after every failed attempt.
+            http_retry_backoff_ms: parse_env("HTTP_RETRY_BACKOFF_MS", 500),
+            // Reads the "HTTP_USER_AGENT" environment variable or defaults to the crate name and version.
+            http_user_agent: env::var("HTTP_USER_AGENT").unwrap_or_else(|_| {
+                format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+            }),
+            // Reads the "HTTP_PROXY" environment variable; no proxy is used if not found.
+            http_proxy: env::var("HTTP_PROXY").ok().filter(|v| !v.is_empty()),
+            // Reads the "CIRCUIT_BREAKER_FAILURE_THRESHOLD" environment variable or defaults to 3.
+            // This many consecutive scrape failures open the circuit.
+            circuit_breaker_failure_threshold: parse_env("CIRCUIT_BREAKER_FAILURE_THRESHOLD", 3),
+            // Reads the "CIRCUIT_BREAKER_RESET_TIMEOUT_SECS" environment variable or defaults to 300 seconds.
+            // This is how long the circuit stays open before a probe scrape is allowed.
+            circuit_breaker_reset_timeout_secs: parse_env(
+                "CIRCUIT_BREAKER_RESET_TIMEOUT_SECS",
+                300,
+            ),
+            // Reads the "CRAWL_RESPECT_ROBOTS" environment variable or defaults to true.
+            crawl_respect_robots: parse_env("CRAWL_RESPECT_ROBOTS", true),
+            // Reads the "CRAWL_MIN_INTERVAL_MS" environment variable or defaults to 2000 milliseconds.
+            // This is the minimum time between two requests to the same host.
+            crawl_min_interval_ms: parse_env("CRAWL_MIN_INTERVAL_MS", 2000),
+            // Reads the "CRAWL_CACHE_TTL_SECS" environment variable or defaults to 3600 seconds.
+            // Fetched pages are reused for this long; 0 disables the page cache.
+            crawl_cache_ttl_secs: parse_env("CRAWL_CACHE_TTL_SECS", 3600),
+            // Reads the "BACKFILL_CONCURRENCY" environment variable or defaults to 2.
+            // This is the maximum number of years scraped at the same time by a batch scrape.
+            backfill_concurrency: parse_env("BACKFILL_CONCURRENCY", 2),
+            // Reads the "CACHE_ENABLED" environment variable or defaults to true.
+            // With file storage, holidays are served from memory and reloaded when the files change.
+            cache_enabled: parse_env("CACHE_ENABLED", true),
+            // Reads the "ADMIN_TOKEN" environment variable, a secret accepted as an API key with the admin scope.
+            admin_token: env::var("ADMIN_TOKEN").ok().filter(|v| !v.is_empty()),
+            // Reads the "API_KEYS" environment variable, a comma-separated list of `name:scopes:secret` keys,
+            // e.g. "ci:read+scrape:s3cret". Secrets may be given as "sha256:<hex>" instead of in plain text.
+            api_keys: env::var("API_KEYS").ok().filter(|v| !v.is_empty()),
+            // Reads the "API_KEY_CACHE_TTL_SECS" environment variable or defaults to 30 seconds.
+            // Stored API keys are looked up in memory and reloaded this often, so keys created or revoked
+            // by another replica take effect within this time.
+            api_key_cache_ttl_secs: parse_env("API_KEY_CACHE_TTL_SECS", 30),
+            // Reads the "PUBLIC_SCOPES" environment variable or defaults to "read".
+            // These scopes are granted to requests without an API key; set it to "" to require a key everywhere.
+            public_scopes: env::var("PUBLIC_SCOPES").unwrap_or_else(|_| "read".to_string()),
+            // Reads the "RATE_LIMIT_READ_PER_MINUTE" environment variable or defaults to 120.
+            // This is how many read requests a client (API key or IP address) may make per minute; 0 disables the limit.
+            rate_limit_read_per_minute: parse_env("RATE_LIMIT_READ_PER_MINUTE", 120),
+            // Reads the "RATE_LIMIT_SCRAPE_PER_MINUTE" environment variable or defaults to 6.
+            rate_limit_scrape_per_minute: parse_env("RATE_LIMIT_SCRAPE_PER_MINUTE", 6),
+            // Reads the "RATE_LIMIT_ADMIN_PER_MINUTE" environment variable or defaults to 60.
+            rate_limit_admin_per_minute: parse_env("RATE_LIMIT_ADMIN_PER_MINUTE", 60),
+            // Reads the "RATE_LIMIT_IP_PER_MINUTE" environment variable or defaults to 300.
+            // This is how many requests an IP address may make per minute, checked before its API key is looked at.
+            rate_limit_ip_per_minute: parse_env("RATE_LIMIT_IP_PER_MINUTE", 300),
+            // Reads the "RATE_LIMIT_DAILY_QUOTA" environment variable or defaults to 0 (no quota).
+            // This is how many requests a client (API key or IP address) may make per day, across all scopes.
+            rate_limit_daily_quota: parse_env("RATE_LIMIT_DAILY_QUOTA", 0),
+            // Reads the "TRUSTED_PROXIES" environment variable, a comma-separated list of addresses and CIDR ranges
+            // of reverse proxies whose X-Forwarded-For header is believed. Empty (the default) ignores the header.
+            trusted_proxies: env::var("TRUSTED_PROXIES").unwrap_or_default(),
+            // Reads the "SCRAPE_MERGE_STRATEGY" environment variable or defaults to "merge-keep-manual".
+            // This is how scrapes are merged into the stored holidays unless a request asks otherwise.
+            scrape_merge_strategy: env::var("SCRAPE_MERGE_STRATEGY")
+                .unwrap_or_else(|_| "merge-keep-manual".to_string()),
+            // Reads the "TIMEZONE" environment variable or defaults to "Asia/Jakarta" (WIB).
+            // The scheduler and "today" run in this time zone, whatever the host's time zone is.
+            timezone: env::var("TIMEZONE").unwrap_or_else(|_| "Asia/Jakarta".to_string()),
+            // Reads the "SCHEDULER_ENABLED" environment variable or defaults to true.
+            scheduler_enabled: parse_env("SCHEDULER_ENABLED", true),
+            // Reads the "SCHEDULER_JOBS" environment variable, a semicolon-separated list of `job=cron` entries,
+            // e.g. "scrape-current-year=1 0 1 1 *;validate-data=30 2 * * *". Only the listed jobs are scheduled;
+            // an empty value schedules every job with its default cron expression.
+            scheduler_jobs: env::var("SCHEDULER_JOBS").unwrap_or_default(),
+            // Reads the "SHUTDOWN_TIMEOUT_SECS" environment variable or defaults to 30 seconds.
+            // On SIGTERM or SIGINT, in-flight requests and background tasks together get this long to finish,
+            // counted from the signal.
+            shutdown_timeout_secs: parse_env("SHUTDOWN_TIMEOUT_SECS", 30),
+            // Reads the "INSTANCE_ID" environment variable or defaults to the host name.
+            // Scrape jobs record the instance running them, and on startup only this instance's jobs are requeued,
+            // so instances sharing one host must set distinct values.
+            instance_id: env::var("INSTANCE_ID")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(host_name),
         }
     }
 }
+
+// Reads an environment variable and parses it, falling back to the default if it is missing or invalid.
+fn parse_env<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+// Returns the host name from the HOSTNAME environment variable or /etc/hostname, or "localhost" if neither is set.
+fn host_name() -> String {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
@@ -0,0 +1,70 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// ApiKeyScope is a permission granted to an API key.
+// Scopes are ordered: admin includes scrape, and scrape includes read.
+// - Read: Reading holiday data, scrape jobs and the health of the scraper.
+// - Scrape: Triggering scrapes of the external site.
+// - Admin: Correcting holiday data and managing API keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyScope {
+    Read,
+    Scrape,
+    Admin,
+}
+
+impl ApiKeyScope {
+    // All scopes, as accepted in API_KEYS, PUBLIC_SCOPES and the key management endpoints.
+    pub const ALL: [ApiKeyScope; 3] = [ApiKeyScope::Read, ApiKeyScope::Scrape, ApiKeyScope::Admin];
+
+    // Parses a scope name, e.g. "scrape". Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        Self::ALL.into_iter().find(|scope| scope.as_str() == value)
+    }
+
+    // Returns the scope as a lowercase string, used in configuration and API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::Read => "read",
+            ApiKeyScope::Scrape => "scrape",
+            ApiKeyScope::Admin => "admin",
+        }
+    }
+
+    // Checks whether this scope includes the required one.
+    pub fn grants(&self, required: ApiKeyScope) -> bool {
+        *self >= required
+    }
+}
+
+// ApiKey is a credential that grants scoped access to the API.
+// Only the SHA-256 hash of the secret is kept, so stored keys cannot be read back.
+// It contains:
+// - id: The key id, used to revoke it.
+// - name: A label naming the key's owner, e.g. "ci" or "mobile-app".
+// - key_hash: The hex-encoded SHA-256 hash of the secret.
+// - scopes: The scopes granted to the key.
+// - created_at: When the key was created.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: NaiveDateTime,
+}
+
+impl ApiKey {
+    // Hashes a secret for storage and lookup.
+    pub fn hash_secret(secret: &str) -> String {
+        format!("{:x}", Sha256::digest(secret.as_bytes()))
+    }
+
+    // Checks whether the key grants the required scope.
+    pub fn allows(&self, required: ApiKeyScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+}
@@ -1,28 +1,45 @@
+use crate::domain::entities::holiday_category::HolidayCategory;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+// A holiday as stored in the repository.
+// `is_manual` marks holidays added or corrected by an override. It is set when the overrides are applied
+// and never stored, so the stored holidays are always the scraped ones.
 #[derive(Clone, PartialEq, Eq)]
 pub struct Holiday {
     pub date: NaiveDate,
     pub description: String,
     pub is_joint_leave: bool,
+    pub is_manual: bool,
 }
 
 impl Holiday {
-    // Constructor to create a new Holiday instance.
+    // Constructor to create a new (not manually corrected) Holiday instance.
     pub fn new(date: NaiveDate, description: String, is_joint_leave: bool) -> Self {
         Self {
             date,
             description,
             is_joint_leave,
+            is_manual: false,
         }
     }
 
+    // Marks the holiday as added or corrected by an override.
+    pub fn corrected(mut self) -> Self {
+        self.is_manual = true;
+        self
+    }
+
     // Getter method to check if the holiday is a joint leave.
     pub fn is_joint_leave(&self) -> bool {
         self.is_joint_leave
     }
 
+    // Method to classify the holiday (national, islamic, christian, ...) from its description.
+    pub fn category(&self) -> HolidayCategory {
+        HolidayCategory::from_description(&self.description)
+    }
+
     // Method to format the date as a string in the "YYYY-MM-DD" format.
     pub fn format_date(&self) -> String {
         self.date.format("%Y-%m-%d").to_string()
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+// HolidayCategory is the kind of holiday, derived from its description.
+// Joint leave days (Cuti Bersama) take the category of the holiday they extend.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum HolidayCategory {
+    National,
+    Islamic,
+    Christian,
+    Hindu,
+    Buddhist,
+    Confucian,
+    Other,
+}
+
+// Keywords identifying each category, matched case-insensitively against the description.
+// Religious categories are checked before national ones, so e.g. "Tahun Baru Islam" is not
+// mistaken for the national "Tahun Baru" (New Year).
+const KEYWORDS: &[(HolidayCategory, &[&str])] = &[
+    (
+        HolidayCategory::Islamic,
+        &[
+            "idul",
+            "isra",
+            "mi'raj",
+            "miraj",
+            "maulid",
+            "tahun baru islam",
+            "hijriah",
+            "muharram",
+        ],
+    ),
+    (
+        HolidayCategory::Christian,
+        &[
+            "natal",
+            "isa al masih",
+            "yesus",
+            "kristus",
+            "paskah",
+            "jumat agung",
+        ],
+    ),
+    (HolidayCategory::Hindu, &["nyepi", "saka"]),
+    (HolidayCategory::Buddhist, &["waisak", "waisyak"]),
+    (HolidayCategory::Confucian, &["imlek", "kongzili"]),
+    (
+        HolidayCategory::National,
+        &[
+            "tahun baru",
+            "kemerdekaan",
+            "proklamasi",
+            "pancasila",
+            "buruh",
+            "pemilu",
+            "pilkada",
+            "pemilihan",
+        ],
+    ),
+];
+
+impl HolidayCategory {
+    // All categories, in the order they are listed in API responses.
+    pub const ALL: [HolidayCategory; 7] = [
+        HolidayCategory::National,
+        HolidayCategory::Islamic,
+        HolidayCategory::Christian,
+        HolidayCategory::Hindu,
+        HolidayCategory::Buddhist,
+        HolidayCategory::Confucian,
+        HolidayCategory::Other,
+    ];
+
+    // Classifies a holiday by its description. Descriptions matching no keyword are `Other`.
+    pub fn from_description(description: &str) -> Self {
+        let description = description.to_lowercase();
+        KEYWORDS
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|k| description.contains(k)))
+            .map(|(category, _)| *category)
+            .unwrap_or(HolidayCategory::Other)
+    }
+
+    // Parses a category name as used in query parameters, e.g. "islamic". Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == value)
+    }
+
+    // Returns the category as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HolidayCategory::National => "national",
+            HolidayCategory::Islamic => "islamic",
+            HolidayCategory::Christian => "christian",
+            HolidayCategory::Hindu => "hindu",
+            HolidayCategory::Buddhist => "buddhist",
+            HolidayCategory::Confucian => "confucian",
+            HolidayCategory::Other => "other",
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::domain::entities::holiday::Holiday;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// MergeStrategy decides how freshly scraped holidays are combined with the stored ones and with the
+// manual corrections (overrides) of the year. A scrape never deletes overrides; only an admin withdraws them.
+// - Replace: The scrape replaces the stored holidays; the overrides still apply, but the ones the scrape
+//   contradicts are reported for review.
+// - MergeKeepManual: The scrape replaces the stored holidays; the overrides are kept and still apply.
+// - AddOnly: Stored holidays are kept as they are and only new scraped ones are added; the overrides are kept.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeStrategy {
+    Replace,
+    MergeKeepManual,
+    AddOnly,
+}
+
+impl MergeStrategy {
+    // All strategies, as accepted by the `strategy` parameter.
+    pub const ALL: [MergeStrategy; 3] = [
+        MergeStrategy::Replace,
+        MergeStrategy::MergeKeepManual,
+        MergeStrategy::AddOnly,
+    ];
+
+    // Parses a strategy name, e.g. "merge-keep-manual". Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.as_str() == value)
+    }
+
+    // Returns the strategy name, used in configuration and API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeStrategy::Replace => "replace",
+            MergeStrategy::MergeKeepManual => "merge-keep-manual",
+            MergeStrategy::AddOnly => "add-only",
+        }
+    }
+}
+
+// MergeConflict is a scraped holiday that the overrides of its date remove or change.
+// It contains:
+// - date: The date in question.
+// - manual: The descriptions on that date with the overrides applied.
+// - scraped: The scraped description.
+// - resolution: "kept_manual" if the overrides win, "review_manual" if they still win but should be reviewed (Replace).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MergeConflict {
+    pub date: NaiveDate,
+    pub manual: Vec<String>,
+    pub scraped: String,
+    pub resolution: String,
+}
+
+// MergeOutcome is the result of merging a scrape into the stored holidays of a year.
+// It contains the holidays to store and the conflicts that were found.
+#[derive(Clone)]
+pub struct MergeOutcome {
+    pub holidays: Vec<Holiday>,
+    pub conflicts: Vec<MergeConflict>,
+}
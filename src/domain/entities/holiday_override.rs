@@ -0,0 +1,67 @@
+use crate::domain::entities::holiday::Holiday;
+use crate::shared::utils::text_utils::normalize_for_search;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+// OverrideAction is what a manual correction does to the scraped holidays of its date.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum OverrideAction {
+    Add,
+    Edit,
+    Remove,
+}
+
+impl OverrideAction {
+    // Parses an action name, e.g. "edit". Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            OverrideAction::Add,
+            OverrideAction::Edit,
+            OverrideAction::Remove,
+        ]
+        .into_iter()
+        .find(|action| action.as_str() == value)
+    }
+
+    // Returns the action as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverrideAction::Add => "add",
+            OverrideAction::Edit => "edit",
+            OverrideAction::Remove => "remove",
+        }
+    }
+}
+
+// HolidayOverride is a manual correction layered over the scraped holidays of a year.
+// Overrides are stored separately from the scraped data, so they survive later scrapes.
+// It contains:
+// - id: The override id, used to withdraw it.
+// - year / date: The date the correction applies to.
+// - action: Add a holiday, edit the matching holidays, or remove the matching holidays.
+// - match_description: For edit and remove, only holidays with this description are affected
+//   (compared ignoring case and diacritics); all holidays on the date if not set.
+// - description / is_joint_leave: The new values for add and edit.
+// - created_at: When the override was made; overrides are applied oldest first.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HolidayOverride {
+    pub id: String,
+    pub year: i32,
+    pub date: NaiveDate,
+    pub action: OverrideAction,
+    pub match_description: Option<String>,
+    pub description: Option<String>,
+    pub is_joint_leave: Option<bool>,
+    pub created_at: NaiveDateTime,
+}
+
+impl HolidayOverride {
+    // Checks whether a holiday is targeted by this override's edit or remove.
+    pub fn matches(&self, holiday: &Holiday) -> bool {
+        holiday.date == self.date
+            && self.match_description.as_deref().is_none_or(|description| {
+                normalize_for_search(description) == normalize_for_search(&holiday.description)
+            })
+    }
+}
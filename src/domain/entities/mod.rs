@@ -1 +1,8 @@
-pub mod holiday;
\ No newline at end of file
+pub mod api_key;
+pub mod holiday;
+pub mod holiday_category;
+pub mod holiday_merge;
+pub mod holiday_override;
+pub mod scheduled_job;
+pub mod scrape_job;
+pub mod scraper_health;
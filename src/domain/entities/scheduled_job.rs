@@ -0,0 +1,246 @@
+use crate::shared::errors::app_error::{AppError, AppResult};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone};
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+
+// The first month of the fourth quarter, from which the next year is pre-fetched.
+pub const PREFETCH_FROM_MONTH: u32 = 10;
+
+// ScheduledJobKind is what a scheduled job does when it runs.
+// - ScrapeCurrentYear: Scrape the current year, e.g. on January 1st.
+// - PrefetchNextYear: Scrape the next year during Q4, when the joint decree (SKB) for it is published.
+//   Runs outside Q4 are skipped.
+// - Rescrape: Scrape the current year again (and the next year, if it has data) to pick up revisions.
+// - ValidateData: Check the stored holidays of every year for inconsistencies.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScheduledJobKind {
+    ScrapeCurrentYear,
+    PrefetchNextYear,
+    Rescrape,
+    ValidateData,
+}
+
+impl ScheduledJobKind {
+    // All job kinds, as accepted in SCHEDULER_JOBS.
+    pub const ALL: [ScheduledJobKind; 4] = [
+        ScheduledJobKind::ScrapeCurrentYear,
+        ScheduledJobKind::PrefetchNextYear,
+        ScheduledJobKind::Rescrape,
+        ScheduledJobKind::ValidateData,
+    ];
+
+    // Parses a job kind, e.g. "validate-data". Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    // Returns the job kind as a string, used as the job name in configuration and API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledJobKind::ScrapeCurrentYear => "scrape-current-year",
+            ScheduledJobKind::PrefetchNextYear => "prefetch-next-year",
+            ScheduledJobKind::Rescrape => "rescrape",
+            ScheduledJobKind::ValidateData => "validate-data",
+        }
+    }
+
+    // Returns the cron expression used when SCHEDULER_JOBS does not configure the job kinds.
+    pub fn default_schedule(&self) -> &'static str {
+        match self {
+            // 00:01 on January 1st
+            ScheduledJobKind::ScrapeCurrentYear => "1 0 1 1 *",
+            // 03:00 every Monday from October to December
+            ScheduledJobKind::PrefetchNextYear => "0 3 * 10-12 1",
+            // 04:00 every Sunday
+            ScheduledJobKind::Rescrape => "0 4 * * 0",
+            // 02:30 every day
+            ScheduledJobKind::ValidateData => "30 2 * * *",
+        }
+    }
+}
+
+// The windows, in days before now, that are searched for a missed run.
+// The last one is unbounded in practice: a job that was not due for ten years is not caught up.
+const CATCH_UP_WINDOWS_DAYS: [i64; 4] = [1, 31, 366, 3660];
+
+// ScheduledJob is a job run by the scheduler whenever its cron expression matches.
+// It contains:
+// - kind: What the job does; it also names the job.
+// - schedule: The cron expression ("minute hour day-of-month month day-of-week").
+// - cron: The parsed schedule.
+#[derive(Clone)]
+pub struct ScheduledJob {
+    pub kind: ScheduledJobKind,
+    pub schedule: String,
+    cron: Cron,
+}
+
+impl ScheduledJob {
+    // Constructor to create a job, parsing its cron expression.
+    // Returns a Configuration error if the expression is invalid, since schedules come from SCHEDULER_JOBS.
+    pub fn new(kind: ScheduledJobKind, schedule: &str) -> AppResult<Self> {
+        let schedule = schedule.trim().to_string();
+        let cron = Cron::new(&schedule).parse().map_err(|e| {
+            AppError::Configuration(format!(
+                "Invalid cron expression '{}' for job {}: {}",
+                schedule,
+                kind.as_str(),
+                e
+            ))
+        })?;
+
+        Ok(Self {
+            kind,
+            schedule,
+            cron,
+        })
+    }
+
+    // Returns the job name.
+    pub fn name(&self) -> &'static str {
+        self.kind.as_str()
+    }
+
+    // Returns the first time after `after` at which the job is due, or None if the schedule never matches again.
+    pub fn next_run_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        self.cron.find_next_occurrence(after, false).ok()
+    }
+
+    // Returns the last time in `(after, until]` at which the job was due, or None if it was not due in between.
+    // Recent windows are searched first, so frequent schedules are not walked all the way back to `after`.
+    pub fn latest_run_between<Tz: TimeZone>(
+        &self,
+        after: &DateTime<Tz>,
+        until: &DateTime<Tz>,
+    ) -> Option<DateTime<Tz>> {
+        for days in CATCH_UP_WINDOWS_DAYS {
+            let window_start = until.clone() - Duration::days(days);
+            let from = if window_start > *after {
+                window_start
+            } else {
+                after.clone()
+            };
+
+            let mut latest = None;
+            let mut next = self.next_run_after(&from);
+            while let Some(run) = next.filter(|run| run <= until) {
+                next = self.next_run_after(&run);
+                latest = Some(run);
+            }
+
+            if latest.is_some() || from == *after {
+                return latest;
+            }
+        }
+        None
+    }
+}
+
+// JobRunStatus is the outcome of a scheduled job run.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum JobRunStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+impl JobRunStatus {
+    // Whether the run completed the job: a skipped run had nothing to do, so it counts as successful.
+    pub fn is_successful(&self) -> bool {
+        matches!(self, JobRunStatus::Succeeded | JobRunStatus::Skipped)
+    }
+
+    // Returns the status as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobRunStatus::Succeeded => "succeeded",
+            JobRunStatus::Failed => "failed",
+            JobRunStatus::Skipped => "skipped",
+        }
+    }
+
+    // Parses a status as returned by as_str. Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            JobRunStatus::Succeeded,
+            JobRunStatus::Failed,
+            JobRunStatus::Skipped,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == value)
+    }
+}
+
+// JobRun is one run of a scheduled job, kept as run history.
+// It contains:
+// - id: The run id.
+// - job: The name of the job.
+// - scheduled_for: The time the run was due, in the service time zone.
+// - started_at / finished_at: When the run actually started and finished.
+// - status: Whether the run succeeded, failed or was skipped.
+// - message: What the run did, or why it failed or was skipped.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JobRun {
+    pub id: String,
+    pub job: String,
+    pub scheduled_for: NaiveDateTime,
+    pub started_at: NaiveDateTime,
+    pub finished_at: NaiveDateTime,
+    pub status: JobRunStatus,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().to_utc()
+    }
+
+    #[test]
+    fn latest_run_between_returns_the_last_due_time() {
+        let job = ScheduledJob::new(ScheduledJobKind::ValidateData, "30 2 * * *").unwrap();
+
+        let latest =
+            job.latest_run_between(&at("2025-03-01T00:00:00Z"), &at("2025-03-10T12:00:00Z"));
+
+        assert_eq!(latest, Some(at("2025-03-10T02:30:00Z")));
+    }
+
+    #[test]
+    fn latest_run_between_excludes_after_and_includes_until() {
+        let job = ScheduledJob::new(ScheduledJobKind::ValidateData, "30 2 * * *").unwrap();
+
+        assert_eq!(
+            job.latest_run_between(&at("2025-03-10T02:30:00Z"), &at("2025-03-11T02:29:00Z")),
+            None
+        );
+        assert_eq!(
+            job.latest_run_between(&at("2025-03-10T02:30:00Z"), &at("2025-03-11T02:30:00Z")),
+            Some(at("2025-03-11T02:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn latest_run_between_searches_past_the_recent_windows() {
+        // Due once a year, so the one- and 31-day windows are empty.
+        let job = ScheduledJob::new(ScheduledJobKind::ScrapeCurrentYear, "1 0 1 1 *").unwrap();
+
+        let latest =
+            job.latest_run_between(&at("2020-06-01T00:00:00Z"), &at("2025-12-31T00:00:00Z"));
+
+        assert_eq!(latest, Some(at("2025-01-01T00:01:00Z")));
+    }
+
+    #[test]
+    fn invalid_cron_expression_is_a_configuration_error() {
+        let result = ScheduledJob::new(ScheduledJobKind::ValidateData, "61 * * * *");
+
+        assert!(matches!(result, Err(AppError::Configuration(_))));
+    }
+}
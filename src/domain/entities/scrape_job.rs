@@ -0,0 +1,118 @@
+use crate::domain::entities::holiday_merge::{MergeConflict, MergeStrategy};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+// JobStatus is the lifecycle state of a scrape job.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStatus {
+    // Returns the status as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        }
+    }
+
+    // Parses a status as returned by as_str. Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            JobStatus::Queued,
+            JobStatus::Running,
+            JobStatus::Succeeded,
+            JobStatus::Failed,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == value)
+    }
+}
+
+// ScrapeJob is a request to scrape the holidays of one year in the background.
+// It is persisted so queued and interrupted jobs survive a restart.
+// `strategy` is the requested merge strategy (None for the default), `owner` is the instance (INSTANCE_ID) that
+// claimed the job to run it, and `conflicts` are the conflicts with manual corrections found by a successful run.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScrapeJob {
+    pub id: String,
+    pub year: i32,
+    #[serde(default)]
+    pub strategy: Option<MergeStrategy>,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub owner: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+    pub holidays_scraped: Option<usize>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl ScrapeJob {
+    // Constructor to create a new queued job for the given year and merge strategy.
+    pub fn new(
+        id: String,
+        year: i32,
+        strategy: Option<MergeStrategy>,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            year,
+            strategy,
+            status: JobStatus::Queued,
+            owner: None,
+            created_at,
+            started_at: None,
+            finished_at: None,
+            holidays_scraped: None,
+            error: None,
+            conflicts: Vec::new(),
+        }
+    }
+
+    // Marks the job as running on the given instance.
+    pub fn start(&mut self, owner: &str, now: NaiveDateTime) {
+        self.status = JobStatus::Running;
+        self.owner = Some(owner.to_string());
+        self.started_at = Some(now);
+    }
+
+    // Marks the job as succeeded with the number of holidays scraped and the merge conflicts found.
+    pub fn succeed(
+        &mut self,
+        holidays_scraped: usize,
+        conflicts: Vec<MergeConflict>,
+        now: NaiveDateTime,
+    ) {
+        self.status = JobStatus::Succeeded;
+        self.finished_at = Some(now);
+        self.holidays_scraped = Some(holidays_scraped);
+        self.error = None;
+        self.conflicts = conflicts;
+    }
+
+    // Marks the job as failed with the given error message.
+    pub fn fail(&mut self, error: String, now: NaiveDateTime) {
+        self.status = JobStatus::Failed;
+        self.finished_at = Some(now);
+        self.error = Some(error);
+    }
+
+    // Puts an interrupted job back in the queue, e.g. after a restart.
+    pub fn requeue(&mut self) {
+        self.status = JobStatus::Queued;
+        self.owner = None;
+        self.started_at = None;
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::NaiveDateTime;
+
+// HealthStatus describes how confident we are that the external source can still be scraped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+}
+
+impl HealthStatus {
+    // Returns the status as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Degraded => "degraded",
+        }
+    }
+}
+
+// CircuitState is the state of the circuit breaker guarding calls to the external source.
+// - Closed: Calls go through normally.
+// - Open: Calls fail fast until the reset timeout has elapsed.
+// - HalfOpen: A single probe call is allowed through to check whether the source has recovered.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    // Returns the state as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+// CircuitBreakerStatus is a snapshot of the circuit breaker.
+// It contains:
+// - state: The current circuit state.
+// - consecutive_failures: How many calls failed in a row.
+// - opened_at: When the circuit was last opened (None if it never opened).
+// - retry_at: When the next probe call will be allowed (only set while open).
+#[derive(Clone)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub opened_at: Option<NaiveDateTime>,
+    pub retry_at: Option<NaiveDateTime>,
+}
+
+// ScraperHealth is a snapshot of the scraping service's health.
+// It contains:
+// - status: Whether the scraper is healthy or degraded.
+// - reasons: Human-readable explanations when the status is degraded.
+// - checked_at: When the status was last evaluated (None if no scrape has run yet).
+// - circuit_breaker: The circuit breaker status, if the scraper is guarded by one.
+#[derive(Clone)]
+pub struct ScraperHealth {
+    pub status: HealthStatus,
+    pub reasons: Vec<String>,
+    pub checked_at: Option<NaiveDateTime>,
+    pub circuit_breaker: Option<CircuitBreakerStatus>,
+}
+
+impl ScraperHealth {
+    // Creates a healthy status that has not been checked yet.
+    pub fn healthy() -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            reasons: Vec::new(),
+            checked_at: None,
+            circuit_breaker: None,
+        }
+    }
+}
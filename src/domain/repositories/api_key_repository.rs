@@ -0,0 +1,19 @@
+use crate::domain::entities::api_key::ApiKey;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    // Async method to store a new API key.
+    async fn save_key(&self, api_key: ApiKey) -> AppResult<()>;
+
+    // Async method to list all stored API keys, oldest first.
+    async fn list_keys(&self) -> AppResult<Vec<ApiKey>>;
+
+    // Async method to find the API key with the given secret hash.
+    async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>>;
+
+    // Async method to delete an API key by id.
+    // Returns a NotFound error if no key with that id exists.
+    async fn delete_key(&self, id: &str) -> AppResult<()>;
+}
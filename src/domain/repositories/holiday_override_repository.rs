@@ -0,0 +1,19 @@
+use crate::domain::entities::holiday_override::HolidayOverride;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait HolidayOverrideRepository: Send + Sync {
+    // Async method to store a new override.
+    async fn save_override(&self, holiday_override: HolidayOverride) -> AppResult<()>;
+
+    // Async method to list the overrides of a year, oldest first.
+    async fn list_overrides(&self, year: i32) -> AppResult<Vec<HolidayOverride>>;
+
+    // Async method to list the years that have at least one override, in ascending order.
+    async fn list_override_years(&self) -> AppResult<Vec<i32>>;
+
+    // Async method to delete an override of a year by id.
+    // Returns a NotFound error if the year has no override with that id.
+    async fn delete_override(&self, year: i32, id: &str) -> AppResult<()>;
+}
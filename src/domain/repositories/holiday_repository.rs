@@ -1,6 +1,11 @@
 use crate::domain::entities::holiday::Holiday;
 use crate::shared::errors::app_error::AppResult;
 use async_trait::async_trait;
+use chrono::NaiveDate;
+
+// A change to the holidays of a year: it receives the stored holidays (empty if the year has not been saved)
+// and returns the holidays to store instead.
+pub type HolidayUpdate = Box<dyn FnOnce(Vec<Holiday>) -> Vec<Holiday> + Send>;
 
 #[async_trait]
 pub trait HolidayRepository: Send + Sync {
@@ -15,4 +20,27 @@ pub trait HolidayRepository: Send + Sync {
     // Async method to check if holidays exist for a given year.
     // Returns a `Result` with a boolean value (`true` if holidays exist for the year, otherwise `false`).
     async fn holidays_exist_for_year(&self, year: i32) -> AppResult<bool>;
+
+    // Async method to list every year that has been saved, in ascending order.
+    async fn list_available_years(&self) -> AppResult<Vec<i32>>;
+
+    // Async method to retrieve the holidays between two dates (both inclusive), across years, ordered by date.
+    // Years that have not been saved simply contribute no holidays.
+    async fn get_holidays_in_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<Holiday>>;
+
+    // Async method to retrieve the holidays on a specific date (there may be several, or none).
+    async fn find_by_date(&self, date: NaiveDate) -> AppResult<Vec<Holiday>>;
+
+    // Async method to delete all holidays of a year.
+    // Returns a NotFound error if the year has never been saved.
+    async fn delete_year(&self, year: i32) -> AppResult<()>;
+
+    // Async method to read, change and save the holidays of a year as one step.
+    // No other write to the year can happen in between, so concurrent updates never overwrite each other.
+    // The year is created if it has not been saved yet.
+    async fn update_year(&self, year: i32, update: HolidayUpdate) -> AppResult<()>;
 }
@@ -0,0 +1,15 @@
+use crate::domain::entities::scheduled_job::JobRun;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait JobRunRepository: Send + Sync {
+    // Async method to record a finished run of a scheduled job.
+    async fn save_run(&self, run: JobRun) -> AppResult<()>;
+
+    // Async method to list the most recent runs, newest first, optionally only those of one job.
+    async fn list_runs(&self, job: Option<&str>, limit: usize) -> AppResult<Vec<JobRun>>;
+
+    // Async method to find the most recent successful run of a job, which the scheduler catches up from.
+    async fn last_successful_run(&self, job: &str) -> AppResult<Option<JobRun>>;
+}
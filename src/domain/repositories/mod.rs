@@ -1 +1,5 @@
-pub mod holiday_repository;
\ No newline at end of file
+pub mod api_key_repository;
+pub mod holiday_override_repository;
+pub mod holiday_repository;
+pub mod job_run_repository;
+pub mod scrape_job_repository;
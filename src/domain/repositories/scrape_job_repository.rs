@@ -0,0 +1,25 @@
+use crate::domain::entities::scrape_job::ScrapeJob;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+#[async_trait]
+pub trait ScrapeJobRepository: Send + Sync {
+    // Async method to insert or update a scrape job, keyed by its id.
+    async fn save_job(&self, job: ScrapeJob) -> AppResult<()>;
+
+    // Async method to retrieve a scrape job by id.
+    // Returns `None` if no job with that id exists.
+    async fn get_job(&self, id: &str) -> AppResult<Option<ScrapeJob>>;
+
+    // Async method to claim the oldest queued job for the given instance, marking it as running by that instance.
+    // The claim is a single atomic step, so two workers, also of different replicas, never claim the same job.
+    // Returns `None` if no job is queued.
+    async fn claim_next_job(&self, owner: &str, now: NaiveDateTime)
+        -> AppResult<Option<ScrapeJob>>;
+
+    // Async method to put the jobs still running on the given instance back in the queue, e.g. after it restarted.
+    // Running jobs without an owner, recorded before owners were, are requeued too.
+    // Returns the number of jobs that were requeued.
+    async fn requeue_jobs_of(&self, owner: &str) -> AppResult<usize>;
+}
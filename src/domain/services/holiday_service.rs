@@ -1,14 +1,137 @@
 use crate::domain::entities::holiday::Holiday;
-use crate::shared::errors::app_error::AppResult;
+use crate::domain::entities::holiday_category::HolidayCategory;
+use crate::domain::entities::holiday_merge::{MergeConflict, MergeOutcome, MergeStrategy};
+use crate::domain::entities::holiday_override::{HolidayOverride, OverrideAction};
+use crate::domain::entities::scraper_health::ScraperHealth;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::text_utils::normalize_for_search;
 use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[async_trait]
 pub trait HolidayScrapingService: Send + Sync {
     // Async method to scrape holidays for a given year.
     // It returns a vector of `Holiday` objects for the specified year, or an error (`AppResult`).
     async fn scrape_holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>>;
+
+    // Method to report the current health of the scraping service.
+    // Implementations that cannot detect problems on their own are always healthy.
+    fn health(&self) -> ScraperHealth {
+        ScraperHealth::healthy()
+    }
+
+    // Async method to accept a changed layout of the external source as the new baseline, clearing the alert.
+    // Implementations that do not track the layout have nothing to accept.
+    async fn accept_layout(&self) -> AppResult<()> {
+        Err(AppError::NotFound(
+            "The scraping service does not track layout changes".to_string(),
+        ))
+    }
+}
+
+// HolidayGrouping is the attribute holidays are grouped by.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HolidayGrouping {
+    Type,
+    Month,
+    Weekday,
+    Quarter,
+    Category,
+}
+
+impl HolidayGrouping {
+    // All groupings, as accepted by the `by` query parameter.
+    pub const ALL: [HolidayGrouping; 5] = [
+        HolidayGrouping::Type,
+        HolidayGrouping::Month,
+        HolidayGrouping::Weekday,
+        HolidayGrouping::Quarter,
+        HolidayGrouping::Category,
+    ];
+
+    // Parses a grouping name, e.g. "month". Returns None if unknown.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|grouping| grouping.as_str() == value)
+    }
+
+    // Returns the grouping as a lowercase string, used in API responses.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HolidayGrouping::Type => "type",
+            HolidayGrouping::Month => "month",
+            HolidayGrouping::Weekday => "weekday",
+            HolidayGrouping::Quarter => "quarter",
+            HolidayGrouping::Category => "category",
+        }
+    }
+
+    // Returns the keys of every group, in the order the groups are listed.
+    fn keys(&self) -> Vec<&'static str> {
+        match self {
+            HolidayGrouping::Type => TYPE_KEYS.to_vec(),
+            HolidayGrouping::Month => MONTH_KEYS.to_vec(),
+            HolidayGrouping::Weekday => WEEKDAY_KEYS.to_vec(),
+            HolidayGrouping::Quarter => QUARTER_KEYS.to_vec(),
+            HolidayGrouping::Category => HolidayCategory::ALL.map(|c| c.as_str()).to_vec(),
+        }
+    }
+
+    // Returns the key of the group a holiday belongs to.
+    fn key_of(&self, holiday: &Holiday) -> &'static str {
+        match self {
+            HolidayGrouping::Type if holiday.is_joint_leave() => TYPE_KEYS[0],
+            HolidayGrouping::Type => TYPE_KEYS[1],
+            HolidayGrouping::Month => MONTH_KEYS[holiday.date.month0() as usize],
+            HolidayGrouping::Weekday => {
+                WEEKDAY_KEYS[holiday.date.weekday().num_days_from_monday() as usize]
+            }
+            HolidayGrouping::Quarter => QUARTER_KEYS[holiday.date.month0() as usize / 3],
+            HolidayGrouping::Category => holiday.category().as_str(),
+        }
+    }
 }
 
+// Group keys for holiday types, joint leave first.
+const TYPE_KEYS: [&str; 2] = ["joint_leave", "non_joint_leave"];
+
+// Group keys for quarters of the year.
+const QUARTER_KEYS: [&str; 4] = ["q1", "q2", "q3", "q4"];
+
+// Group keys for months, January first.
+const MONTH_KEYS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+// Group keys for weekdays, Monday first (ISO order).
+const WEEKDAY_KEYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+// A year with fewer holidays than this is most likely an incomplete scrape.
+const MIN_HOLIDAYS_PER_YEAR: usize = 10;
+
+#[derive(Default, Clone)]
 pub struct HolidayDomainService;
 
 impl HolidayDomainService {
@@ -17,21 +140,354 @@ impl HolidayDomainService {
         Self
     }
 
-    // Method to group holidays into two categories: joint leave and non-joint leave.
-    // It takes a vector of `Holiday` and returns a tuple containing two vectors: one for joint leaves and another for non-joint leaves.
-    pub fn group_holidays_by_type(&self, holidays: Vec<Holiday>) -> (Vec<Holiday>, Vec<Holiday>) {
-        let mut joint_leave = Vec::new();
-        let mut non_joint_leave = Vec::new();
+    // Method to group holidays by the given attribute.
+    // Every possible group is returned, in its natural order (e.g. January to December), even when empty;
+    // within a group, holidays keep their original order.
+    pub fn group_holidays(
+        &self,
+        holidays: Vec<Holiday>,
+        grouping: HolidayGrouping,
+    ) -> Vec<(&'static str, Vec<Holiday>)> {
+        let mut groups: Vec<(&'static str, Vec<Holiday>)> = grouping
+            .keys()
+            .into_iter()
+            .map(|key| (key, Vec::new()))
+            .collect();
+
+        for holiday in holidays {
+            let key = grouping.key_of(&holiday);
+            if let Some((_, group)) = groups.iter_mut().find(|(k, _)| *k == key) {
+                group.push(holiday);
+            }
+        }
+
+        groups
+    }
+
+    // Method to apply manual overrides to a list of holidays, oldest override first.
+    // Every override only affects its own date, so the holidays may be a whole year or any part of it.
+    // The result is ordered by date.
+    pub fn apply_overrides(
+        &self,
+        mut holidays: Vec<Holiday>,
+        overrides: &[HolidayOverride],
+    ) -> Vec<Holiday> {
+        let mut overrides: Vec<&HolidayOverride> = overrides.iter().collect();
+        overrides.sort_by_key(|holiday_override| holiday_override.created_at);
+
+        for holiday_override in overrides {
+            match holiday_override.action {
+                OverrideAction::Add => {
+                    let description = holiday_override.description.clone().unwrap_or_default();
+                    let is_joint_leave = holiday_override
+                        .is_joint_leave
+                        .unwrap_or_else(|| description.contains("Cuti Bersama"));
+                    holidays.push(
+                        Holiday::new(holiday_override.date, description, is_joint_leave)
+                            .corrected(),
+                    );
+                }
+                OverrideAction::Edit => {
+                    for holiday in holidays
+                        .iter_mut()
+                        .filter(|holiday| holiday_override.matches(holiday))
+                    {
+                        if let Some(description) = &holiday_override.description {
+                            holiday.description = description.clone();
+                        }
+                        if let Some(is_joint_leave) = holiday_override.is_joint_leave {
+                            holiday.is_joint_leave = is_joint_leave;
+                        }
+                        holiday.is_manual = true;
+                    }
+                }
+                OverrideAction::Remove => {
+                    holidays.retain(|holiday| !holiday_override.matches(holiday));
+                }
+            }
+        }
+
+        holidays.sort_by_key(|holiday| holiday.date);
+        holidays
+    }
+
+    // Method to merge freshly scraped holidays into the stored holidays of a year, following the strategy.
+    // Manual corrections are never stored with the holidays: they stay in the overrides, which are layered
+    // over the stored holidays when they are read. A scraped holiday that the overrides of the year remove
+    // or change is reported as a conflict. The overrides are never withdrawn here, so the corrections still
+    // win; with Replace the conflicts are flagged for review, so an admin can withdraw the outdated ones.
+    // The merged holidays are ordered by date.
+    pub fn merge_scraped(
+        &self,
+        existing: Vec<Holiday>,
+        scraped: Vec<Holiday>,
+        overrides: &[HolidayOverride],
+        strategy: MergeStrategy,
+    ) -> MergeOutcome {
+        let same = |a: &Holiday, b: &Holiday| {
+            a.date == b.date
+                && normalize_for_search(&a.description) == normalize_for_search(&b.description)
+        };
+
+        let mut holidays: Vec<Holiday> = match strategy {
+            MergeStrategy::Replace | MergeStrategy::MergeKeepManual => scraped.clone(),
+            MergeStrategy::AddOnly => {
+                let new: Vec<Holiday> = scraped
+                    .iter()
+                    .filter(|holiday| !existing.iter().any(|stored| same(stored, holiday)))
+                    .cloned()
+                    .collect();
+                existing.into_iter().chain(new).collect()
+            }
+        };
+        holidays.sort_by_key(|holiday| holiday.date);
+
+        // The holidays as they are read, with the corrections applied
+        let corrected = self.apply_overrides(holidays.clone(), overrides);
+        let corrected_dates: BTreeSet<NaiveDate> = overrides
+            .iter()
+            .map(|holiday_override| holiday_override.date)
+            .collect();
+
+        let conflicts = scraped
+            .iter()
+            .filter(|holiday| corrected_dates.contains(&holiday.date))
+            .filter(|holiday| {
+                !corrected.iter().any(|read| {
+                    same(read, holiday) && read.is_joint_leave == holiday.is_joint_leave
+                })
+            })
+            .map(|holiday| MergeConflict {
+                date: holiday.date,
+                manual: corrected
+                    .iter()
+                    .filter(|read| read.date == holiday.date)
+                    .map(|read| read.description.clone())
+                    .collect(),
+                scraped: holiday.description.clone(),
+                resolution: match strategy {
+                    MergeStrategy::Replace => "review_manual",
+                    _ => "kept_manual",
+                }
+                .to_string(),
+            })
+            .collect();
+
+        MergeOutcome {
+            holidays,
+            conflicts,
+        }
+    }
+
+    // Method to check the stored holidays of a year for inconsistencies.
+    // Returns a description of every issue found: holidays dated outside the year, holidays without a
+    // description, the same holiday stored twice, and years with suspiciously few holidays.
+    pub fn validate_holidays(&self, year: i32, holidays: &[Holiday]) -> Vec<String> {
+        let mut issues = Vec::new();
 
         for holiday in holidays {
-            // Classify holidays based on the `is_joint_leave` flag.
-            if holiday.is_joint_leave() {
-                joint_leave.push(holiday);
-            } else {
-                non_joint_leave.push(holiday);
+            if holiday.date.year() != year {
+                issues.push(format!(
+                    "{} '{}' is stored under year {}",
+                    holiday.format_date(),
+                    holiday.description,
+                    year
+                ));
+            }
+            if holiday.description.trim().is_empty() {
+                issues.push(format!("{} has no description", holiday.format_date()));
             }
         }
 
-        (joint_leave, non_joint_leave)
+        // Holidays by date and normalized description, so differences in case or accents still count as duplicates
+        let mut seen: BTreeMap<(NaiveDate, String), Vec<&Holiday>> = BTreeMap::new();
+        for holiday in holidays {
+            seen.entry((holiday.date, normalize_for_search(&holiday.description)))
+                .or_default()
+                .push(holiday);
+        }
+        for duplicates in seen.values().filter(|duplicates| duplicates.len() > 1) {
+            issues.push(format!(
+                "{} '{}' is stored {} times",
+                duplicates[0].format_date(),
+                duplicates[0].description,
+                duplicates.len()
+            ));
+        }
+
+        if holidays.len() < MIN_HOLIDAYS_PER_YEAR {
+            issues.push(format!(
+                "Year {} has only {} holidays, expected at least {}",
+                year,
+                holidays.len(),
+                MIN_HOLIDAYS_PER_YEAR
+            ));
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn holiday(month: u32, day: u32, description: &str) -> Holiday {
+        Holiday::new(date(month, day), description.to_string(), false)
+    }
+
+    fn override_of(
+        minute: u32,
+        day: u32,
+        action: OverrideAction,
+        match_description: Option<&str>,
+        description: Option<&str>,
+    ) -> HolidayOverride {
+        HolidayOverride {
+            id: format!("override-{}", minute),
+            year: 2025,
+            date: date(1, day),
+            action,
+            match_description: match_description.map(str::to_string),
+            description: description.map(str::to_string),
+            is_joint_leave: None,
+            created_at: date(1, 1).and_hms_opt(0, minute, 0).unwrap(),
+        }
+    }
+
+    fn descriptions(holidays: &[Holiday]) -> Vec<&str> {
+        holidays.iter().map(|h| h.description.as_str()).collect()
+    }
+
+    #[test]
+    fn apply_overrides_adds_edits_and_removes_in_creation_order() {
+        let service = HolidayDomainService::new();
+        let holidays = vec![
+            holiday(1, 27, "Isra Mikraj"),
+            holiday(1, 1, "Tahun Baru"),
+            holiday(1, 29, "Imlek"),
+        ];
+        let overrides = [
+            // Created last, so it edits the holiday the add below introduced.
+            override_of(
+                3,
+                28,
+                OverrideAction::Edit,
+                None,
+                Some("Cuti Bersama Imlek"),
+            ),
+            override_of(1, 28, OverrideAction::Add, None, Some("Cuti Bersama")),
+            override_of(2, 27, OverrideAction::Remove, Some("isra mikraj"), None),
+        ];
+
+        let result = service.apply_overrides(holidays, &overrides);
+
+        assert_eq!(
+            descriptions(&result),
+            ["Tahun Baru", "Cuti Bersama Imlek", "Imlek"]
+        );
+        // Added holidays are joint leave when their description says so.
+        assert!(result[1].is_joint_leave);
+    }
+
+    #[test]
+    fn apply_overrides_only_touches_matching_descriptions() {
+        let service = HolidayDomainService::new();
+        let holidays = vec![holiday(1, 1, "Tahun Baru"), holiday(1, 1, "Other")];
+        let overrides = [override_of(
+            1,
+            1,
+            OverrideAction::Edit,
+            Some("TAHUN BARU"),
+            Some("Tahun Baru Masehi"),
+        )];
+
+        let result = service.apply_overrides(holidays, &overrides);
+
+        assert_eq!(descriptions(&result), ["Tahun Baru Masehi", "Other"]);
+    }
+
+    #[test]
+    fn apply_overrides_marks_added_and_edited_holidays_as_manual() {
+        let service = HolidayDomainService::new();
+        let holidays = vec![holiday(1, 1, "Tahun Baru"), holiday(1, 2, "Other")];
+        let overrides = [
+            override_of(1, 1, OverrideAction::Edit, None, Some("Tahun Baru Masehi")),
+            override_of(2, 3, OverrideAction::Add, None, Some("Added")),
+        ];
+
+        let result = service.apply_overrides(holidays, &overrides);
+
+        let manual: Vec<bool> = result.iter().map(|h| h.is_manual).collect();
+        assert_eq!(manual, [true, false, true]);
+    }
+
+    #[test]
+    fn merge_keep_manual_stores_the_scrape_and_keeps_the_overrides() {
+        let service = HolidayDomainService::new();
+        let existing = vec![holiday(1, 1, "Old scrape"), holiday(3, 31, "Old scrape")];
+        let scraped = vec![holiday(1, 1, "New Year"), holiday(3, 31, "Idul Fitri")];
+        let overrides = [override_of(
+            1,
+            1,
+            OverrideAction::Edit,
+            None,
+            Some("Tahun Baru"),
+        )];
+
+        let outcome = service.merge_scraped(
+            existing,
+            scraped,
+            &overrides,
+            MergeStrategy::MergeKeepManual,
+        );
+
+        assert_eq!(descriptions(&outcome.holidays), ["New Year", "Idul Fitri"]);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].date, date(1, 1));
+        assert_eq!(outcome.conflicts[0].manual, ["Tahun Baru"]);
+        assert_eq!(outcome.conflicts[0].resolution, "kept_manual");
+    }
+
+    #[test]
+    fn merge_replace_flags_the_contradicted_overrides_for_review() {
+        let service = HolidayDomainService::new();
+        let scraped = vec![holiday(1, 1, "New Year")];
+        let overrides = [
+            override_of(1, 1, OverrideAction::Remove, None, None),
+            override_of(2, 5, OverrideAction::Add, None, Some("Added")),
+        ];
+
+        let outcome =
+            service.merge_scraped(Vec::new(), scraped, &overrides, MergeStrategy::Replace);
+
+        // The stored holidays are the scrape; the overrides still apply when they are read.
+        assert_eq!(descriptions(&outcome.holidays), ["New Year"]);
+        let read = service.apply_overrides(outcome.holidays.clone(), &overrides);
+        assert_eq!(descriptions(&read), ["Added"]);
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert!(outcome.conflicts[0].manual.is_empty());
+        assert_eq!(outcome.conflicts[0].resolution, "review_manual");
+    }
+
+    #[test]
+    fn merge_add_only_adds_new_entries_only() {
+        let service = HolidayDomainService::new();
+        let existing = vec![holiday(1, 1, "Tahun Baru")];
+        let scraped = vec![holiday(1, 1, "TAHUN BARU"), holiday(3, 31, "Idul Fitri")];
+        // The scraped holiday agrees with the corrected data, so this is not a conflict.
+        let overrides = [override_of(1, 1, OverrideAction::Add, None, Some("Other"))];
+
+        let outcome = service.merge_scraped(existing, scraped, &overrides, MergeStrategy::AddOnly);
+
+        assert_eq!(
+            descriptions(&outcome.holidays),
+            ["Tahun Baru", "Idul Fitri"]
+        );
+        assert!(outcome.conflicts.is_empty());
     }
 }
@@ -0,0 +1,38 @@
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait LeaderElection: Send + Sync {
+    // Async method to become the leader, or to check that this instance still is.
+    // Returns whether this instance is the leader. Leadership is lost automatically when the
+    // leader's process dies, so another instance takes over on its next attempt.
+    async fn try_acquire(&self) -> AppResult<bool>;
+
+    // Async method to give up leadership, e.g. when shutting down, so another instance can take over right away.
+    async fn release(&self) -> AppResult<()>;
+}
+
+// LeadershipChange is the outcome of a leader election round.
+// - Acquired: This instance just became the leader.
+// - Kept: This instance was and still is the leader.
+// - Lost: This instance was the leader but no longer is.
+// - Follower: Another instance is the leader.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LeadershipChange {
+    Acquired,
+    Kept,
+    Lost,
+    Follower,
+}
+
+impl LeadershipChange {
+    // Determines the change from whether this instance was and is the leader.
+    pub fn from_transition(was_leader: bool, is_leader: bool) -> Self {
+        match (was_leader, is_leader) {
+            (false, true) => LeadershipChange::Acquired,
+            (true, true) => LeadershipChange::Kept,
+            (true, false) => LeadershipChange::Lost,
+            (false, false) => LeadershipChange::Follower,
+        }
+    }
+}
@@ -1 +1,2 @@
-pub mod holiday_service;
\ No newline at end of file
+pub mod holiday_service;
+pub mod leader_election;
\ No newline at end of file
@@ -0,0 +1,210 @@
+use crate::config::Config;
+use crate::domain::entities::holiday::Holiday;
+use crate::domain::entities::scraper_health::{
+    CircuitBreakerStatus, CircuitState, HealthStatus, ScraperHealth,
+};
+use crate::domain::services::holiday_service::HolidayScrapingService;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::now_in;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use chrono_tz::Tz;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// BreakerState holds the mutable state of the circuit breaker.
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<NaiveDateTime>,
+    open_until: Option<Instant>,
+    probe_started: Option<Instant>,
+}
+
+// CircuitBreakerScrapingService wraps another scraping service with a circuit breaker.
+// After `failure_threshold` consecutive failures of the source (transport errors, timeouts and 5xx responses,
+// which the HTTP client reports as ExternalService errors) the circuit opens and scrapes fail fast.
+// Other errors, such as a year that is not published yet or a path disallowed by robots.txt, prove the source
+// answered and count as successes.
+// Once `reset_timeout` has elapsed the next scrape is let through as a probe (half-open):
+// if it succeeds the circuit closes again, otherwise it re-opens for another timeout.
+pub struct CircuitBreakerScrapingService {
+    inner: Arc<dyn HolidayScrapingService>,
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    timezone: Tz,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreakerScrapingService {
+    // Constructor to wrap a scraping service, using the thresholds from the configuration.
+    // The opening and retry times it reports are in the given service time zone.
+    pub fn new(inner: Arc<dyn HolidayScrapingService>, config: &Config, timezone: Tz) -> Self {
+        Self {
+            inner,
+            failure_threshold: config.circuit_breaker_failure_threshold.max(1),
+            reset_timeout: Duration::from_secs(config.circuit_breaker_reset_timeout_secs),
+            timezone,
+            state: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                open_until: None,
+                probe_started: None,
+            }),
+        }
+    }
+
+    // Decides whether a call may go through, moving from open to half-open once the timeout has elapsed.
+    // Returns an error when the call should fail fast.
+    fn acquire(&self) -> AppResult<()> {
+        let mut breaker = self.lock_state()?;
+
+        match breaker.state {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => {
+                let ready = breaker
+                    .open_until
+                    .map(|until| Instant::now() >= until)
+                    .unwrap_or(true);
+                if !ready {
+                    return Err(self.open_error(&breaker));
+                }
+                println!("🔌 Circuit breaker half-open, probing the external source");
+                breaker.state = CircuitState::HalfOpen;
+                breaker.probe_started = Some(Instant::now());
+                Ok(())
+            }
+            CircuitState::HalfOpen => {
+                // Only one probe at a time; everything else fails fast until it completes.
+                // A probe that never reported back (e.g. its request was cancelled) is replaced after the timeout.
+                let probe_running = breaker
+                    .probe_started
+                    .map(|started| started.elapsed() < self.reset_timeout)
+                    .unwrap_or(false);
+                if probe_running {
+                    return Err(AppError::ServiceUnavailable(
+                        "External holiday source is being probed after repeated failures"
+                            .to_string(),
+                    ));
+                }
+                breaker.probe_started = Some(Instant::now());
+                Ok(())
+            }
+        }
+    }
+
+    // Records the outcome of a call that was let through.
+    fn record(&self, success: bool) {
+        let Ok(mut breaker) = self.lock_state() else {
+            return;
+        };
+        breaker.probe_started = None;
+
+        if success {
+            if breaker.state != CircuitState::Closed {
+                println!("🔌 Circuit breaker closed, external source recovered");
+            }
+            breaker.state = CircuitState::Closed;
+            breaker.consecutive_failures = 0;
+            breaker.open_until = None;
+            return;
+        }
+
+        breaker.consecutive_failures += 1;
+        if breaker.state == CircuitState::HalfOpen
+            || breaker.consecutive_failures >= self.failure_threshold
+        {
+            eprintln!(
+                "🔌 Circuit breaker opened after {} consecutive failures, retrying in {:?}",
+                breaker.consecutive_failures, self.reset_timeout
+            );
+            breaker.state = CircuitState::Open;
+            breaker.opened_at = Some(now_in(self.timezone));
+            breaker.open_until = Some(Instant::now() + self.reset_timeout);
+        }
+    }
+
+    // Returns a snapshot of the circuit breaker for status reporting.
+    pub fn status(&self) -> CircuitBreakerStatus {
+        match self.lock_state() {
+            Ok(breaker) => CircuitBreakerStatus {
+                state: breaker.state,
+                consecutive_failures: breaker.consecutive_failures,
+                opened_at: breaker.opened_at,
+                retry_at: self.retry_at(&breaker),
+            },
+            Err(_) => CircuitBreakerStatus {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                retry_at: None,
+            },
+        }
+    }
+
+    // Converts the monotonic reopen deadline into a wall-clock time while the circuit is open.
+    fn retry_at(&self, breaker: &BreakerState) -> Option<NaiveDateTime> {
+        if breaker.state != CircuitState::Open {
+            return None;
+        }
+        let remaining = breaker
+            .open_until?
+            .saturating_duration_since(Instant::now());
+        let remaining = chrono::Duration::from_std(remaining).ok()?;
+        Some(now_in(self.timezone) + remaining)
+    }
+
+    // Builds the fail-fast error returned while the circuit is open.
+    fn open_error(&self, breaker: &BreakerState) -> AppError {
+        let retry_at = self
+            .retry_at(breaker)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        AppError::ServiceUnavailable(format!(
+            "External holiday source is unavailable after {} consecutive failures, retry after {}",
+            breaker.consecutive_failures, retry_at
+        ))
+    }
+
+    // Locks the breaker state, mapping a poisoned lock to an internal error.
+    fn lock_state(&self) -> AppResult<std::sync::MutexGuard<'_, BreakerState>> {
+        self.state
+            .lock()
+            .map_err(|_| AppError::InternalServer("Circuit breaker state poisoned".to_string()))
+    }
+}
+
+#[async_trait]
+impl HolidayScrapingService for CircuitBreakerScrapingService {
+    // Scrapes through the wrapped service unless the circuit is open.
+    async fn scrape_holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+        self.acquire()?;
+
+        let result = self.inner.scrape_holidays_for_year(year).await;
+        self.record(!matches!(result, Err(AppError::ExternalService(_))));
+        result
+    }
+
+    // Reports the wrapped service's health, degraded while the circuit is not closed.
+    fn health(&self) -> ScraperHealth {
+        let mut health = self.inner.health();
+        let status = self.status();
+
+        if status.state != CircuitState::Closed {
+            health.status = HealthStatus::Degraded;
+            health.reasons.push(format!(
+                "Circuit breaker is {} after {} consecutive scrape failures",
+                status.state.as_str(),
+                status.consecutive_failures
+            ));
+        }
+        health.circuit_breaker = Some(status);
+        health
+    }
+
+    // Accepts a changed layout through the wrapped service; this is not a call to the external source.
+    async fn accept_layout(&self) -> AppResult<()> {
+        self.inner.accept_layout().await
+    }
+}
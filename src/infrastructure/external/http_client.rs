@@ -0,0 +1,244 @@
+use crate::config::Config;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use reqwest::{Client, Proxy, StatusCode};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// The longest Retry-After we wait for; a server asking for more makes the request fail instead.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+// CachedPage holds the last successful response for a URL together with its validators,
+// so later requests can be made conditional and a "304 Not Modified" can be served from memory.
+#[derive(Clone)]
+struct CachedPage {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+// FailedAttempt describes why a single request failed.
+// It contains:
+// - error: The error to report if no retry succeeds.
+// - retryable: Whether the failure is worth retrying.
+// - retry_after: How long the server asked us to wait before retrying, from its Retry-After header.
+struct FailedAttempt {
+    error: AppError,
+    retryable: bool,
+    retry_after: Option<Duration>,
+}
+
+// HttpClient wraps a reqwest client configured from `Config`.
+// It applies connect/read timeouts, a custom User-Agent and an optional proxy,
+// retries transient failures with exponential backoff and sends conditional requests.
+pub struct HttpClient {
+    client: Client,
+    max_retries: u32,
+    retry_backoff: Duration,
+    cache: Mutex<HashMap<String, CachedPage>>,
+}
+
+impl HttpClient {
+    // Builds the HTTP client from the configuration.
+    // Returns an error if the proxy URL is invalid or the client cannot be created.
+    pub fn new(config: &Config) -> AppResult<Self> {
+        let mut builder = Client::builder()
+            .connect_timeout(Duration::from_secs(config.http_connect_timeout_secs))
+            .read_timeout(Duration::from_secs(config.http_read_timeout_secs))
+            .user_agent(config.http_user_agent.clone());
+
+        if let Some(proxy_url) = &config.http_proxy {
+            let proxy = Proxy::all(proxy_url)
+                .map_err(|e| AppError::InternalServer(format!("Invalid HTTP proxy: {}", e)))?;
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(Self {
+            client: builder.build()?,
+            max_retries: config.http_max_retries,
+            retry_backoff: Duration::from_millis(config.http_retry_backoff_ms),
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    // Fetches the body of the given URL as text.
+    // Server errors, rate limiting, timeouts and connection failures are retried with exponential backoff;
+    // other client errors fail immediately. A `Retry-After` header on a 429 or 503 response is honoured,
+    // unless it asks for more than MAX_RETRY_AFTER, in which case the request fails right away.
+    // `wait_turn` is awaited before every attempt, retries included, with the minimum delay before the attempt;
+    // the caller uses it to keep every request inside its rate limit.
+    pub async fn get_text<F, Fut>(&self, url: &str, mut wait_turn: F) -> AppResult<String>
+    where
+        F: FnMut(Duration) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let mut last_error = AppError::ExternalService(format!("No request made to {}", url));
+        let mut delay = Duration::ZERO;
+
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                println!(
+                    "Retrying {} in {:?} (attempt {} of {})",
+                    url,
+                    delay,
+                    attempt + 1,
+                    self.max_retries + 1
+                );
+            }
+            wait_turn(delay).await;
+
+            match self.send(url).await {
+                Ok(body) => return Ok(body),
+                Err(failure) => {
+                    eprintln!("Request to {} failed: {}", url, failure.error);
+                    if !failure.retryable {
+                        return Err(failure.error);
+                    }
+                    let backoff = self.retry_backoff * 2u32.saturating_pow(attempt);
+                    delay = match failure.retry_after {
+                        Some(retry_after) if retry_after > MAX_RETRY_AFTER => {
+                            return Err(failure.error);
+                        }
+                        Some(retry_after) => retry_after.max(backoff),
+                        None => backoff,
+                    };
+                    last_error = failure.error;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    // Sends a single (conditional) request.
+    async fn send(&self, url: &str) -> Result<String, FailedAttempt> {
+        let cached = self.cached_page(url);
+
+        let mut request = self.client.get(url);
+        if let Some(page) = &cached {
+            if let Some(etag) = &page.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &page.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send().await.map_err(|e| FailedAttempt {
+            retryable: e.is_timeout() || e.is_connect(),
+            error: AppError::from(e),
+            retry_after: None,
+        })?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(page) = cached {
+                println!("{} not modified, using cached copy", url);
+                return Ok(page.body);
+            }
+        }
+        if !status.is_success() {
+            let retry_after = match status {
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| parse_retry_after(v, Utc::now())),
+                _ => None,
+            };
+            return Err(FailedAttempt {
+                error: status_error(url, status),
+                retryable: status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+                retry_after,
+            });
+        }
+
+        let header_value = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let etag = header_value(ETAG);
+        let last_modified = header_value(LAST_MODIFIED);
+
+        let body = response.text().await.map_err(|e| FailedAttempt {
+            retryable: e.is_timeout() || e.is_body(),
+            error: AppError::from(e),
+            retry_after: None,
+        })?;
+
+        if etag.is_some() || last_modified.is_some() {
+            if let Ok(mut cache) = self.cache.lock() {
+                cache.insert(
+                    url.to_string(),
+                    CachedPage {
+                        etag,
+                        last_modified,
+                        body: body.clone(),
+                    },
+                );
+            }
+        }
+
+        Ok(body)
+    }
+
+    // Returns the cached response for a URL, if any.
+    fn cached_page(&self, url: &str) -> Option<CachedPage> {
+        self.cache.lock().ok()?.get(url).cloned()
+    }
+}
+
+// Maps an unsuccessful status to an error. Only server errors are an ExternalService failure, like transport
+// errors and timeouts; a missing page, rate limiting or a refused request say nothing about the source being down.
+fn status_error(url: &str, status: StatusCode) -> AppError {
+    let message = format!("{} responded with status {}", url, status);
+    match status {
+        StatusCode::NOT_FOUND | StatusCode::GONE => AppError::NotFound(message),
+        StatusCode::TOO_MANY_REQUESTS => AppError::ServiceUnavailable(message),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => AppError::Forbidden(message),
+        status if status.is_client_error() => AppError::BadRequest(message),
+        _ => AppError::ExternalService(message),
+    }
+}
+
+// Parses a Retry-After header, given either as a number of seconds or as an HTTP date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        let now = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 01 Jan 2025 00:00:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Tue, 31 Dec 2024 23:59:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
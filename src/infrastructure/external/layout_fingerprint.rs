@@ -0,0 +1,136 @@
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Selectors whose hit counts make up the structural part of the fingerprint.
+// These mirror the selectors used by WebScrapingService, so a change in their counts
+// is a strong hint that the parser no longer sees the page the way it expects.
+const TRACKED_SELECTORS: [&str; 4] = [
+    "article",
+    "article ul",
+    "article ul li a",
+    "article ul table",
+];
+
+// A hit count is considered to deviate markedly when it changes by more than this ratio.
+const MAX_COUNT_DEVIATION: f64 = 0.5;
+
+// LayoutFingerprint captures the structure of a scraped tanggalan.com page.
+// It contains:
+// - selector_hits: How many elements each tracked selector matched.
+// - table_shapes: The number of columns of every holiday table, in document order.
+// - month_headers: The set of month names found in the section headers.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LayoutFingerprint {
+    pub selector_hits: BTreeMap<String, usize>,
+    pub table_shapes: Vec<usize>,
+    pub month_headers: BTreeSet<String>,
+}
+
+impl LayoutFingerprint {
+    // Builds a fingerprint from a parsed document.
+    pub fn from_document(document: &Html) -> Self {
+        let mut selector_hits = BTreeMap::new();
+        for selector in TRACKED_SELECTORS {
+            let count = Selector::parse(selector)
+                .map(|s| document.select(&s).count())
+                .unwrap_or_default();
+            selector_hits.insert(selector.to_string(), count);
+        }
+
+        // A table's shape is the widest row it contains, which stays stable across years
+        // while the number of rows naturally varies with the number of holidays.
+        let table_selector = Selector::parse("article ul table").unwrap();
+        let row_selector = Selector::parse("tr").unwrap();
+        let cell_selector = Selector::parse("td, th").unwrap();
+        let table_shapes = document
+            .select(&table_selector)
+            .map(|table| {
+                table
+                    .select(&row_selector)
+                    .map(|tr| tr.select(&cell_selector).count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        // Month headers are the first link of each month section, without the year digits.
+        let ul_selector = Selector::parse("article ul").unwrap();
+        let header_selector = Selector::parse("li a").unwrap();
+        let month_headers = document
+            .select(&ul_selector)
+            .filter_map(|ul| ul.select(&header_selector).next())
+            .map(|a| {
+                a.text()
+                    .collect::<String>()
+                    .to_lowercase()
+                    .replace(char::is_numeric, "")
+                    .trim()
+                    .to_string()
+            })
+            .filter(|month| !month.is_empty())
+            .collect();
+
+        Self {
+            selector_hits,
+            table_shapes,
+            month_headers,
+        }
+    }
+
+    // Compares this fingerprint against a baseline and returns a human-readable reason for
+    // every marked deviation. An empty list means the layout is considered unchanged.
+    pub fn deviations_from(&self, baseline: &LayoutFingerprint) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        for (selector, &expected) in &baseline.selector_hits {
+            let actual = self
+                .selector_hits
+                .get(selector)
+                .copied()
+                .unwrap_or_default();
+            if Self::count_deviates(expected, actual) {
+                reasons.push(format!(
+                    "Selector '{}' matched {} elements, previously {}",
+                    selector, actual, expected
+                ));
+            }
+        }
+
+        // Only the set of column counts matters, not how many tables share the same shape.
+        let expected_shapes: BTreeSet<usize> = baseline.table_shapes.iter().copied().collect();
+        let actual_shapes: BTreeSet<usize> = self.table_shapes.iter().copied().collect();
+        if expected_shapes != actual_shapes {
+            reasons.push(format!(
+                "Table column counts changed from {:?} to {:?}",
+                expected_shapes, actual_shapes
+            ));
+        }
+
+        let missing: Vec<&String> = baseline
+            .month_headers
+            .difference(&self.month_headers)
+            .collect();
+        let unexpected: Vec<&String> = self
+            .month_headers
+            .difference(&baseline.month_headers)
+            .collect();
+        if !missing.is_empty() || !unexpected.is_empty() {
+            reasons.push(format!(
+                "Month headers changed (missing: {:?}, unexpected: {:?})",
+                missing, unexpected
+            ));
+        }
+
+        reasons
+    }
+
+    // Returns true if a count disappeared, appeared, or changed by more than the allowed ratio.
+    fn count_deviates(expected: usize, actual: usize) -> bool {
+        if expected == 0 || actual == 0 {
+            return expected != actual;
+        }
+        let change = (actual as f64 - expected as f64).abs() / expected as f64;
+        change > MAX_COUNT_DEVIATION
+    }
+}
@@ -1 +1,6 @@
-pub mod scraper_service;
\ No newline at end of file
+pub mod circuit_breaker;
+pub mod http_client;
+pub mod layout_fingerprint;
+pub mod polite_fetcher;
+pub mod robots;
+pub mod scraper_service;
@@ -0,0 +1,226 @@
+use crate::config::Config;
+use crate::infrastructure::external::http_client::HttpClient;
+use crate::infrastructure::external::robots::RobotsRules;
+use crate::infrastructure::persistence::file_storage::{run_blocking, write_atomically};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use reqwest::Url;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::fs;
+use tokio::time::sleep_until;
+
+// How long a fetched robots.txt is trusted before it is fetched again.
+const ROBOTS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// CachedRobots holds the parsed robots.txt of a host and when it was fetched.
+struct CachedRobots {
+    rules: Arc<RobotsRules>,
+    fetched_at: Instant,
+}
+
+// FetchedPage is a page returned by the fetcher.
+// It contains:
+// - body: The page as text.
+// - cached: Whether it was served from the page cache rather than fetched from the site.
+pub struct FetchedPage {
+    pub body: String,
+    pub cached: bool,
+}
+
+// PoliteFetcher makes outbound scraping polite towards the source site.
+// Before a page is requested it:
+// - serves the page from the on-disk page cache if it is still fresh,
+// - checks the host's robots.txt and refuses disallowed paths,
+// - waits until the host's rate limit (or robots.txt crawl-delay, if longer) allows another request.
+// Fetched pages are only cached once the caller has checked them (see cache_page), so e.g. the empty page of a
+// year that is not published yet is fetched again on the next scrape.
+pub struct PoliteFetcher {
+    http_client: HttpClient,
+    user_agent: String,
+    respect_robots: bool,
+    min_interval: Duration,
+    cache_dir: PathBuf,
+    cache_ttl: Duration,
+    robots: Mutex<HashMap<String, CachedRobots>>,
+    next_request: Mutex<HashMap<String, Instant>>,
+}
+
+impl PoliteFetcher {
+    // Builds the fetcher and its HTTP client from the configuration.
+    // Pages are cached under the state directory in DATA_DIR.
+    pub fn new(config: &Config) -> AppResult<Self> {
+        Ok(Self {
+            http_client: HttpClient::new(config)?,
+            user_agent: config.http_user_agent.clone(),
+            respect_robots: config.crawl_respect_robots,
+            min_interval: Duration::from_millis(config.crawl_min_interval_ms),
+            cache_dir: PathBuf::from(format!("{}/state/page_cache", config.data_dir)),
+            cache_ttl: Duration::from_secs(config.crawl_cache_ttl_secs),
+            robots: Mutex::new(HashMap::new()),
+            next_request: Mutex::new(HashMap::new()),
+        })
+    }
+
+    // Fetches a page as text, honouring the page cache, robots.txt and the per-host rate limit.
+    pub async fn fetch(&self, url: &str) -> AppResult<FetchedPage> {
+        if let Some(body) = self.read_cache(url).await {
+            println!("Serving {} from page cache", url);
+            return Ok(FetchedPage { body, cached: true });
+        }
+
+        let parsed = Url::parse(url)
+            .map_err(|e| AppError::BadRequest(format!("Invalid URL {}: {}", url, e)))?;
+        let host = parsed.host_str().unwrap_or_default().to_string();
+
+        let rules = self.robots_for(&parsed, &host).await?;
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+        if !rules.is_allowed(&path) {
+            return Err(AppError::Forbidden(format!(
+                "Fetching {} is disallowed by robots.txt",
+                url
+            )));
+        }
+
+        let body = self
+            .http_client
+            .get_text(url, |delay| {
+                self.wait_for_slot(&host, rules.crawl_delay, delay)
+            })
+            .await?;
+
+        Ok(FetchedPage {
+            body,
+            cached: false,
+        })
+    }
+
+    // Returns the robots.txt rules for a host, fetching and caching them if needed.
+    // Following RFC 9309, a robots.txt that does not exist (or is refused with another 4xx status) allows
+    // everything, while one that is unreachable because of a server error, rate limiting or a network failure
+    // disallows everything: the last rules fetched are used if there are any, otherwise the error is returned.
+    async fn robots_for(&self, url: &Url, host: &str) -> AppResult<Arc<RobotsRules>> {
+        if !self.respect_robots {
+            return Ok(Arc::new(RobotsRules::allow_all()));
+        }
+
+        if let Some(rules) = self.cached_robots(host, ROBOTS_TTL) {
+            return Ok(rules);
+        }
+
+        let robots_url = format!("{}://{}/robots.txt", url.scheme(), url.authority());
+        let rules = match self
+            .http_client
+            .get_text(&robots_url, |delay| self.wait_for_slot(host, None, delay))
+            .await
+        {
+            Ok(content) => RobotsRules::parse(&content, &self.user_agent),
+            Err(e @ (AppError::ExternalService(_) | AppError::ServiceUnavailable(_))) => {
+                if let Some(rules) = self.cached_robots(host, Duration::MAX) {
+                    eprintln!(
+                        "Could not fetch {}, using the rules fetched before: {}",
+                        robots_url, e
+                    );
+                    return Ok(rules);
+                }
+                eprintln!(
+                    "Could not fetch {}, not crawling {}: {}",
+                    robots_url, host, e
+                );
+                return Err(e);
+            }
+            Err(e) => {
+                println!(
+                    "No robots.txt at {}, assuming no restrictions: {}",
+                    robots_url, e
+                );
+                RobotsRules::allow_all()
+            }
+        };
+
+        let rules = Arc::new(rules);
+        if let Ok(mut robots) = self.robots.lock() {
+            robots.insert(
+                host.to_string(),
+                CachedRobots {
+                    rules: rules.clone(),
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+        Ok(rules)
+    }
+
+    // Returns the cached robots.txt rules for a host if they were fetched less than `max_age` ago.
+    fn cached_robots(&self, host: &str, max_age: Duration) -> Option<Arc<RobotsRules>> {
+        let robots = self.robots.lock().ok()?;
+        robots
+            .get(host)
+            .filter(|cached| cached.fetched_at.elapsed() < max_age)
+            .map(|cached| cached.rules.clone())
+    }
+
+    // Reserves the next request slot for a host, no earlier than `delay` from now, and sleeps until it is reached.
+    // Slots are spaced by the configured interval or the crawl-delay, whichever is longer,
+    // so concurrent scrapes of the same host are serialised. A retry asking for a delay (backoff or Retry-After)
+    // pushes back the requests queued after it as well.
+    async fn wait_for_slot(&self, host: &str, crawl_delay: Option<Duration>, delay: Duration) {
+        let interval = crawl_delay.unwrap_or_default().max(self.min_interval);
+        let slot = {
+            let Ok(mut next_request) = self.next_request.lock() else {
+                return;
+            };
+            let earliest = Instant::now() + delay;
+            let slot = next_request
+                .get(host)
+                .copied()
+                .filter(|next| *next > earliest)
+                .unwrap_or(earliest);
+            next_request.insert(host.to_string(), slot + interval);
+            slot
+        };
+
+        sleep_until(slot.into()).await;
+    }
+
+    // Returns the cache file path for a URL.
+    fn cache_path(&self, url: &str) -> PathBuf {
+        let name: String = url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.cache_dir.join(format!("{}.html", name))
+    }
+
+    // Reads a page from the cache if caching is enabled and the cached copy is still fresh.
+    async fn read_cache(&self, url: &str) -> Option<String> {
+        if self.cache_ttl.is_zero() {
+            return None;
+        }
+        let path = self.cache_path(url);
+        let modified = fs::metadata(&path).await.ok()?.modified().ok()?;
+        let age = SystemTime::now().duration_since(modified).ok()?;
+        if age > self.cache_ttl {
+            return None;
+        }
+        fs::read_to_string(path).await.ok()
+    }
+
+    // Writes a fetched page to the cache, atomically and under the file's lock, so a crash or a concurrent fetch
+    // never leaves a truncated page to be served. Failures are logged but do not fail the scrape.
+    pub async fn cache_page(&self, url: &str, body: &str) {
+        if self.cache_ttl.is_zero() {
+            return;
+        }
+        let dir = self.cache_dir.to_string_lossy().into_owned();
+        let path = self.cache_path(url).to_string_lossy().into_owned();
+        let body = body.to_string();
+        if let Err(e) = run_blocking(move || write_atomically(&dir, &path, &body)).await {
+            eprintln!("Failed to cache page {}: {}", url, e);
+        }
+    }
+}
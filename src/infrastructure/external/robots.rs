@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+// RobotsRule is a single Allow or Disallow line from a robots.txt group.
+struct RobotsRule {
+    pattern: String,
+    allow: bool,
+}
+
+// RobotsRules holds the rules of robots.txt that apply to our user agent.
+// It contains:
+// - rules: The Allow/Disallow rules of the matching group.
+// - crawl_delay: The requested delay between requests, if the group specifies one.
+pub struct RobotsRules {
+    rules: Vec<RobotsRule>,
+    pub crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    // Rules that allow everything, used when robots.txt is missing or cannot be fetched.
+    pub fn allow_all() -> Self {
+        Self {
+            rules: Vec::new(),
+            crawl_delay: None,
+        }
+    }
+
+    // Parses robots.txt content for the given user agent.
+    // The group naming our user agent token wins; otherwise the "*" group applies.
+    // Agents are compared case-insensitively but exactly, as RFC 9309 requires: a group for "calendar"
+    // does not apply to "calendar-indonesia".
+    pub fn parse(content: &str, user_agent: &str) -> Self {
+        // Only the product token is matched, e.g. "calendar-indonesia" from "calendar-indonesia/0.1.0".
+        let token = user_agent
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+
+        let mut specific: Option<RobotsRules> = None;
+        let mut wildcard: Option<RobotsRules> = None;
+
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut group = RobotsRules::allow_all();
+        let mut in_rules = false;
+
+        // Stores the finished group if it names our user agent or the wildcard.
+        let mut finish_group = |agents: &[String], group: RobotsRules| {
+            let names_us = agents
+                .iter()
+                .any(|a| !a.is_empty() && a != "*" && *a == token);
+            if names_us {
+                specific.get_or_insert(group);
+            } else if agents.iter().any(|a| a == "*") {
+                wildcard.get_or_insert(group);
+            }
+        };
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group.
+                    if in_rules {
+                        finish_group(&group_agents, group);
+                        group = RobotsRules::allow_all();
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow means everything is allowed, so it adds no rule.
+                    if !value.is_empty() {
+                        group.rules.push(RobotsRule {
+                            pattern: value.to_string(),
+                            allow: key == "allow",
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    in_rules = true;
+                    group.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|secs| *secs >= 0.0)
+                        .map(Duration::from_secs_f64);
+                }
+                _ => {}
+            }
+        }
+        finish_group(&group_agents, group);
+
+        specific.or(wildcard).unwrap_or_else(RobotsRules::allow_all)
+    }
+
+    // Checks whether the given path (including query string) may be fetched.
+    // The longest matching rule wins, and Allow wins over Disallow on a tie.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+
+        for rule in &self.rules {
+            if Self::matches(&rule.pattern, path) {
+                let length = rule.pattern.len();
+                best = match best {
+                    Some((best_length, best_allow))
+                        if best_length > length || (best_length == length && best_allow) =>
+                    {
+                        Some((best_length, best_allow))
+                    }
+                    _ => Some((length, rule.allow)),
+                };
+            }
+        }
+
+        best.map(|(_, allow)| allow).unwrap_or(true)
+    }
+
+    // Matches a robots.txt path pattern, supporting "*" wildcards and a trailing "$" anchor.
+    fn matches(pattern: &str, path: &str) -> bool {
+        let (pattern, anchored) = match pattern.strip_suffix('$') {
+            Some(p) => (p, true),
+            None => (pattern, false),
+        };
+
+        let mut parts = pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        if !path.starts_with(first) {
+            return false;
+        }
+
+        let mut position = first.len();
+        let rest: Vec<&str> = parts.collect();
+        for (i, part) in rest.iter().enumerate() {
+            let is_last = i == rest.len() - 1;
+            if is_last && anchored {
+                return path.len() >= position + part.len() && path.ends_with(part);
+            }
+            match path[position..].find(part) {
+                Some(offset) => position += offset + part.len(),
+                None => return false,
+            }
+        }
+
+        !anchored || position == path.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_AGENT: &str = "calendar-indonesia/0.1.0";
+
+    #[test]
+    fn group_naming_our_agent_wins_over_wildcard() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: Calendar-Indonesia\nDisallow: /private\n",
+            USER_AGENT,
+        );
+
+        assert!(rules.is_allowed("/2025"));
+        assert!(!rules.is_allowed("/private/page"));
+    }
+
+    #[test]
+    fn agent_must_match_exactly() {
+        let rules = RobotsRules::parse(
+            "User-agent: calendar\nDisallow: /\n\nUser-agent: indonesia\nDisallow: /\n",
+            USER_AGENT,
+        );
+
+        assert!(rules.is_allowed("/2025"));
+    }
+
+    #[test]
+    fn wildcard_group_applies_when_no_group_names_us() {
+        let rules = RobotsRules::parse(
+            "User-agent: googlebot\nDisallow:\n\nUser-agent: *\nDisallow: /search\nCrawl-delay: 5\n",
+            USER_AGENT,
+        );
+
+        assert!(!rules.is_allowed("/search?q=libur"));
+        assert!(rules.is_allowed("/2025"));
+        assert_eq!(rules.crawl_delay, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn longest_match_wins_and_allow_wins_ties() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\nDisallow: /20\nAllow: /2025\nDisallow: /2025/draft\nAllow: /x\nDisallow: /x\n",
+            USER_AGENT,
+        );
+
+        assert!(rules.is_allowed("/2025"));
+        assert!(!rules.is_allowed("/2024"));
+        assert!(!rules.is_allowed("/2025/draft"));
+        assert!(rules.is_allowed("/x"));
+    }
+
+    #[test]
+    fn patterns_support_wildcards_and_end_anchor() {
+        assert!(RobotsRules::matches("/*.php", "/index.php?page=1"));
+        assert!(RobotsRules::matches("/*.php$", "/index.php"));
+        assert!(!RobotsRules::matches("/*.php$", "/index.php?page=1"));
+        assert!(RobotsRules::matches(
+            "/libur/*/grouped",
+            "/libur/2025/grouped"
+        ));
+        assert!(!RobotsRules::matches("/libur/*/grouped", "/libur/2025"));
+        assert!(RobotsRules::matches("/2025$", "/2025"));
+        assert!(!RobotsRules::matches("/2025$", "/2025/"));
+    }
+}
@@ -1,18 +1,164 @@
+use crate::config::Config;
 use crate::domain::entities::holiday::Holiday;
+use crate::domain::entities::scraper_health::{HealthStatus, ScraperHealth};
 use crate::domain::services::holiday_service::HolidayScrapingService;
+use crate::infrastructure::external::layout_fingerprint::LayoutFingerprint;
+use crate::infrastructure::external::polite_fetcher::PoliteFetcher;
 use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::infrastructure::persistence::file_storage::store_json;
+use crate::shared::utils::date_utils::{get_current_year, now_in};
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
 use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::RwLock;
+
+// FingerprintRecord is the fingerprint of the page structure seen by one scrape.
+#[derive(Serialize, Deserialize, Clone)]
+struct FingerprintRecord {
+    year: i32,
+    scraped_at: NaiveDateTime,
+    fingerprint: LayoutFingerprint,
+}
+
+// LayoutState is the persisted outcome of the layout checks, so an alert survives a restart.
+// It contains:
+// - baseline: The accepted layout that scrapes are compared against.
+// - pending: The latest layout that deviates from the baseline, until an operator accepts it.
+// - reasons: Why the last check found the scraper degraded (empty when healthy).
+// - checked_at: When the layout was last checked.
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct LayoutState {
+    baseline: Option<FingerprintRecord>,
+    pending: Option<FingerprintRecord>,
+    #[serde(default)]
+    reasons: Vec<String>,
+    checked_at: Option<NaiveDateTime>,
+}
+
+impl LayoutState {
+    // Parses the state file. Files written before the state was tracked only hold the baseline fingerprint.
+    fn parse(contents: &str) -> Option<Self> {
+        if let Ok(baseline) = serde_json::from_str::<FingerprintRecord>(contents) {
+            return Some(Self {
+                baseline: Some(baseline),
+                ..Self::default()
+            });
+        }
+        serde_json::from_str(contents).ok()
+    }
+
+    // Derives the health reported for this state.
+    fn health(&self) -> ScraperHealth {
+        ScraperHealth {
+            status: if self.reasons.is_empty() {
+                HealthStatus::Healthy
+            } else {
+                HealthStatus::Degraded
+            },
+            reasons: self.reasons.clone(),
+            checked_at: self.checked_at,
+            circuit_breaker: None,
+        }
+    }
+}
 
 // WebScrapingService struct is responsible for scraping holiday data from a webpage.
-pub struct WebScrapingService;
+// It also tracks the structure of the scraped page, so layout changes on the source site are noticed early.
+pub struct WebScrapingService {
+    fetcher: PoliteFetcher,
+    fingerprint_path: String,
+    layout: RwLock<LayoutState>,
+    timezone: Tz,
+}
 
 impl WebScrapingService {
-    // Constructor to initialize WebScrapingService
-    pub fn new() -> Self {
-        Self
+    // Constructor to initialize WebScrapingService.
+    // Pages are fetched politely (robots.txt, rate limiting, page cache) through an HTTP client
+    // built from the configuration. The accepted layout and the outcome of the last layout check
+    // are stored in the state directory under DATA_DIR and loaded here, so health survives a restart.
+    // The checks are timestamped in the given service time zone.
+    pub fn new(config: &Config, timezone: Tz) -> AppResult<Self> {
+        let fingerprint_path = format!("{}/state/layout_fingerprint.json", config.data_dir);
+        let layout = std::fs::read_to_string(&fingerprint_path)
+            .ok()
+            .and_then(|contents| LayoutState::parse(&contents))
+            .unwrap_or_default();
+
+        Ok(Self {
+            fetcher: PoliteFetcher::new(config)?,
+            fingerprint_path,
+            layout: RwLock::new(layout),
+            timezone,
+        })
+    }
+
+    // Persists the layout state atomically and under the file's lock, so concurrent scrapes of different years
+    // never interleave their writes.
+    async fn store_layout(&self, layout: &LayoutState) -> AppResult<()> {
+        store_json(&self.fingerprint_path, layout).await
+    }
+
+    // Compares the fingerprint of the current scrape with the accepted baseline and updates the health status.
+    // The first scrape that extracts holidays sets the baseline. After that a deviating layout is only recorded
+    // as pending: the baseline stays until an operator accepts the new layout, so the alert does not clear itself
+    // on the next scrape. A scrape that matches the baseline again drops the pending layout.
+    // An empty page of a future year is expected until the year is published, so it is not checked.
+    async fn check_layout(&self, year: i32, fingerprint: LayoutFingerprint, holidays_found: usize) {
+        if holidays_found == 0 && year > get_current_year(self.timezone) {
+            println!(
+                "Year {} is not published yet, its page is not checked against the layout",
+                year
+            );
+            return;
+        }
+
+        let record = FingerprintRecord {
+            year,
+            scraped_at: now_in(self.timezone),
+            fingerprint,
+        };
+
+        let layout = {
+            let Ok(mut layout) = self.layout.write() else {
+                return;
+            };
+            let mut reasons = match &layout.baseline {
+                Some(baseline) => record.fingerprint.deviations_from(&baseline.fingerprint),
+                None => Vec::new(),
+            };
+            if layout.baseline.is_none() {
+                if holidays_found > 0 {
+                    layout.baseline = Some(record.clone());
+                }
+            } else if reasons.is_empty() {
+                layout.pending = None;
+            } else {
+                layout.pending = Some(record.clone());
+            }
+            if holidays_found == 0 {
+                reasons.push(format!("No holidays could be extracted for year {}", year));
+            }
+            layout.reasons = reasons;
+            layout.checked_at = Some(record.scraped_at);
+            layout.clone()
+        };
+
+        if !layout.reasons.is_empty() {
+            eprintln!(
+                "⚠️ Layout change detected on the external holiday site while scraping year {}:",
+                year
+            );
+            for reason in &layout.reasons {
+                eprintln!("   - {}", reason);
+            }
+        }
+
+        if let Err(e) = self.store_layout(&layout).await {
+            eprintln!("Failed to store layout fingerprint: {}", e);
+        }
     }
 
     // Returns a HashMap mapping month names in Indonesian to their respective month codes (e.g., "januari" -> "01").
@@ -33,28 +179,14 @@ impl WebScrapingService {
         ])
     }
 
-    // Parses a date given the year, month code (e.g., "01" for January), and the day as a string.
-    // Returns an `AppResult<NaiveDate>` where errors will be mapped to `AppError`.
-    fn parse_date(&self, year: i32, month_code: &str, day: &str) -> AppResult<NaiveDate> {
-        let date_string = format!("{}-{}-{:0>2}", year, month_code, day.trim());
-        NaiveDate::parse_from_str(&date_string, "%Y-%m-%d")
-            .map_err(|e| AppError::BadRequest(format!("Invalid date format: {}", e)))
-    }
-}
-
-#[async_trait]
-impl HolidayScrapingService for WebScrapingService {
-    // Asynchronously scrapes holiday data for a specific year from an external website.
-    // Returns a list of holidays (`Vec<Holiday>`) or an error if the scraping fails.
-    async fn scrape_holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
-        let url = format!("https://www.tanggalan.com/{}", year); // URL to scrape
-
-        println!("Scraping holidays from: {}", url);
-
-        // Make the HTTP request to the URL
-        let response = reqwest::get(&url).await?;
-        let html_content = response.text().await?;
-        let document = Html::parse_document(&html_content);
+    // Extracts the holidays of a year from the page, along with the fingerprint of the page structure.
+    // The parsed document is not Send, so this stays out of the async scrape.
+    fn extract_holidays(
+        &self,
+        year: i32,
+        html_content: &str,
+    ) -> AppResult<(Vec<Holiday>, LayoutFingerprint)> {
+        let document = Html::parse_document(html_content);
 
         // Define a CSS selector to select the list of holidays for each month
         let ul_selector = Selector::parse("article ul")
@@ -122,14 +254,47 @@ impl HolidayScrapingService for WebScrapingService {
             }
         }
 
-        // If no holidays were found, return an error
+        Ok((holidays, LayoutFingerprint::from_document(&document)))
+    }
+
+    // Parses a date given the year, month code (e.g., "01" for January), and the day as a string.
+    // Returns an `AppResult<NaiveDate>` where errors will be mapped to `AppError`.
+    fn parse_date(&self, year: i32, month_code: &str, day: &str) -> AppResult<NaiveDate> {
+        let date_string = format!("{}-{}-{:0>2}", year, month_code, day.trim());
+        NaiveDate::parse_from_str(&date_string, "%Y-%m-%d")
+            .map_err(|e| AppError::BadRequest(format!("Invalid date format: {}", e)))
+    }
+}
+
+#[async_trait]
+impl HolidayScrapingService for WebScrapingService {
+    // Asynchronously scrapes holiday data for a specific year from an external website.
+    // Returns a list of holidays (`Vec<Holiday>`) or an error if the scraping fails.
+    async fn scrape_holidays_for_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+        let url = format!("https://www.tanggalan.com/{}", year); // URL to scrape
+
+        println!("Scraping holidays from: {}", url);
+
+        // Make the HTTP request to the URL
+        let page = self.fetcher.fetch(&url).await?;
+        let (holidays, fingerprint) = self.extract_holidays(year, &page.body)?;
+
+        // Compare the page structure with the accepted layout to detect layout changes
+        self.check_layout(year, fingerprint, holidays.len()).await;
+
+        // If no holidays were found, return an error; the year is most likely not published yet.
+        // Only pages with holidays are cached, so an unpublished year is fetched again on the next scrape
         if holidays.is_empty() {
-            return Err(AppError::ExternalService(format!(
+            return Err(AppError::NotFound(format!(
                 "No holidays found for year {} from external source",
                 year
             )));
         }
 
+        if !page.cached {
+            self.fetcher.cache_page(&url, &page.body).await;
+        }
+
         println!(
             "Successfully scraped {} holidays for year {}",
             holidays.len(),
@@ -137,4 +302,33 @@ impl HolidayScrapingService for WebScrapingService {
         );
         Ok(holidays)
     }
+
+    // Reports the health derived from the most recent layout check.
+    fn health(&self) -> ScraperHealth {
+        self.layout
+            .read()
+            .map(|layout| layout.health())
+            .unwrap_or_else(|_| ScraperHealth::healthy())
+    }
+
+    // Accepts the pending layout as the new baseline and clears the layout alert.
+    async fn accept_layout(&self) -> AppResult<()> {
+        let layout = {
+            let mut layout = self
+                .layout
+                .write()
+                .map_err(|_| AppError::InternalServer("Layout state poisoned".to_string()))?;
+            let pending = layout.pending.take().ok_or_else(|| {
+                AppError::NotFound("No layout change is waiting to be accepted".to_string())
+            })?;
+            println!(
+                "✅ Accepted the layout seen while scraping year {} as the new baseline",
+                pending.year
+            );
+            layout.baseline = Some(pending);
+            layout.reasons.clear();
+            layout.clone()
+        };
+        self.store_layout(&layout).await
+    }
 }
@@ -0,0 +1,149 @@
+use crate::domain::entities::api_key::ApiKey;
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+// KeySnapshot is the in-memory copy of the stored keys.
+// It contains:
+// - by_hash: Every stored key, keyed by the hash of its secret.
+// - loaded_at: When the keys were read from the wrapped repository.
+struct KeySnapshot {
+    by_hash: HashMap<String, ApiKey>,
+    loaded_at: Instant,
+}
+
+// CachedApiKeyRepository is a repository decorator that answers key lookups from memory, so authenticating a
+// request does not read the key store. The keys are reloaded after a change through this repository and once
+// they are older than the TTL, so keys created or revoked by another replica take effect within the TTL.
+// Every change bumps a generation, so a reload that started before the change does not store its stale keys.
+pub struct CachedApiKeyRepository {
+    inner: Arc<dyn ApiKeyRepository>,
+    ttl: Duration,
+    snapshot: RwLock<Option<KeySnapshot>>,
+    generation: AtomicU64,
+}
+
+impl CachedApiKeyRepository {
+    // Constructor to wrap a repository, keeping the loaded keys for at most the TTL.
+    pub fn new(inner: Arc<dyn ApiKeyRepository>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            snapshot: RwLock::new(None),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    // Looks a key up in the snapshot. Returns None if there is no snapshot or it has expired.
+    fn cached(&self, key_hash: &str) -> Option<Option<ApiKey>> {
+        let snapshot = self.snapshot.read().ok()?;
+        let snapshot = snapshot.as_ref()?;
+        if snapshot.loaded_at.elapsed() >= self.ttl {
+            return None;
+        }
+        Some(snapshot.by_hash.get(key_hash).cloned())
+    }
+
+    // Drops the snapshot, so the next lookup reloads the keys.
+    fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut snapshot) = self.snapshot.write() {
+            *snapshot = None;
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for CachedApiKeyRepository {
+    // Saves through the wrapped repository, then drops the snapshot.
+    async fn save_key(&self, api_key: ApiKey) -> AppResult<()> {
+        self.inner.save_key(api_key).await?;
+        self.invalidate();
+        Ok(())
+    }
+
+    // Lists the keys from the wrapped repository, which is always up to date.
+    async fn list_keys(&self) -> AppResult<Vec<ApiKey>> {
+        self.inner.list_keys().await
+    }
+
+    // Answers from the snapshot, reloading all keys first if it is missing or expired.
+    async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>> {
+        if let Some(api_key) = self.cached(key_hash) {
+            return Ok(api_key);
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let by_hash: HashMap<String, ApiKey> = self
+            .inner
+            .list_keys()
+            .await?
+            .into_iter()
+            .map(|api_key| (api_key.key_hash.clone(), api_key))
+            .collect();
+        let api_key = by_hash.get(key_hash).cloned();
+        if let Ok(mut snapshot) = self.snapshot.write() {
+            if self.generation.load(Ordering::SeqCst) != generation {
+                return Ok(api_key);
+            }
+            *snapshot = Some(KeySnapshot {
+                by_hash,
+                loaded_at: Instant::now(),
+            });
+        }
+        Ok(api_key)
+    }
+
+    // Deletes through the wrapped repository, then drops the snapshot.
+    async fn delete_key(&self, id: &str) -> AppResult<()> {
+        self.inner.delete_key(id).await?;
+        self.invalidate();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::api_key::ApiKeyScope;
+    use crate::infrastructure::persistence::file_api_key_repository::FileApiKeyRepository;
+
+    fn api_key(id: &str, secret: &str) -> ApiKey {
+        ApiKey {
+            id: id.to_string(),
+            name: id.to_string(),
+            key_hash: ApiKey::hash_secret(secret),
+            scopes: vec![ApiKeyScope::Read],
+            created_at: chrono::NaiveDate::from_ymd_opt(2025, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn lookups_are_served_from_memory_until_a_change() {
+        let data_dir = std::env::temp_dir().join(format!("api-keys-{}", uuid::Uuid::new_v4()));
+        let inner = Arc::new(FileApiKeyRepository::new(
+            data_dir.to_string_lossy().into_owned(),
+        ));
+        let cached = CachedApiKeyRepository::new(inner.clone(), Duration::from_secs(60));
+        cached.save_key(api_key("first", "one")).await.unwrap();
+        let hash = ApiKey::hash_secret("one");
+        assert!(cached.find_by_hash(&hash).await.unwrap().is_some());
+
+        // A change behind the cache's back is not seen until the snapshot expires...
+        inner.delete_key("first").await.unwrap();
+        assert!(cached.find_by_hash(&hash).await.unwrap().is_some());
+
+        // ...but a change through the cache is seen at once.
+        cached.save_key(api_key("second", "two")).await.unwrap();
+        let found = cached.find_by_hash(&hash).await;
+        let _ = std::fs::remove_dir_all(&data_dir);
+        assert!(found.unwrap().is_none());
+    }
+}
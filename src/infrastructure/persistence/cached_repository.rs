@@ -0,0 +1,177 @@
+use crate::domain::entities::holiday::Holiday;
+use crate::domain::repositories::holiday_repository::{HolidayRepository, HolidayUpdate};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+// HolidayIndex is the in-memory copy of the stored holidays.
+// It contains:
+// - by_year: The holidays of every loaded year, sorted by date.
+// - by_date: All loaded holidays keyed by date, for date lookups across years.
+#[derive(Default)]
+struct HolidayIndex {
+    by_year: BTreeMap<i32, Vec<Holiday>>,
+    by_date: BTreeMap<NaiveDate, Vec<Holiday>>,
+}
+
+impl HolidayIndex {
+    // Replaces the holidays of a year in both indexes.
+    fn insert_year(&mut self, year: i32, mut holidays: Vec<Holiday>) {
+        self.remove_year(year);
+        holidays.sort_by_key(|holiday| holiday.date);
+        for holiday in &holidays {
+            self.by_date
+                .entry(holiday.date)
+                .or_default()
+                .push(holiday.clone());
+        }
+        self.by_year.insert(year, holidays);
+    }
+
+    // Removes the holidays of a year from both indexes.
+    fn remove_year(&mut self, year: i32) {
+        if let Some(holidays) = self.by_year.remove(&year) {
+            for holiday in holidays {
+                self.by_date.remove(&holiday.date);
+            }
+        }
+    }
+}
+
+// CachedHolidayRepository is a repository decorator that answers reads from an in-memory index.
+// All years are loaded at startup; after a save, or when the underlying files change,
+// the affected year is reloaded from the wrapped repository.
+pub struct CachedHolidayRepository {
+    inner: Arc<dyn HolidayRepository>,
+    index: RwLock<HolidayIndex>,
+}
+
+impl CachedHolidayRepository {
+    // Constructor to wrap a repository with an (initially empty) in-memory index.
+    pub fn new(inner: Arc<dyn HolidayRepository>) -> Self {
+        Self {
+            inner,
+            index: RwLock::new(HolidayIndex::default()),
+        }
+    }
+
+    // Loads the given years into the index. Years that fail to load are logged and skipped.
+    pub async fn load_years(&self, years: Vec<i32>) {
+        let mut loaded = 0;
+        for year in years {
+            if self.reload_year(year).await {
+                loaded += 1;
+            }
+        }
+        println!("📦 Loaded {} year(s) of holidays into memory", loaded);
+    }
+
+    // Reloads a single year from the wrapped repository.
+    // A year that no longer exists is dropped from the index; on any other error the cached copy is kept.
+    // Returns `true` if the year is present in the index afterwards.
+    pub async fn reload_year(&self, year: i32) -> bool {
+        match self.inner.get_holidays_by_year(year).await {
+            Ok(holidays) => {
+                self.write_index(|index| index.insert_year(year, holidays));
+                true
+            }
+            Err(AppError::NotFound(_)) => {
+                self.write_index(|index| index.remove_year(year));
+                false
+            }
+            Err(e) => {
+                eprintln!("Failed to reload holidays for year {}: {}", year, e);
+                self.read_index(|index| index.by_year.contains_key(&year))
+            }
+        }
+    }
+
+    // Runs a closure with read access to the index.
+    fn read_index<T>(&self, read: impl FnOnce(&HolidayIndex) -> T) -> T {
+        match self.index.read() {
+            Ok(index) => read(&index),
+            Err(poisoned) => read(&poisoned.into_inner()),
+        }
+    }
+
+    // Runs a closure with write access to the index.
+    fn write_index(&self, write: impl FnOnce(&mut HolidayIndex)) {
+        match self.index.write() {
+            Ok(mut index) => write(&mut index),
+            Err(poisoned) => write(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+#[async_trait]
+impl HolidayRepository for CachedHolidayRepository {
+    // Saves through the wrapped repository, then reloads the year so the index reflects what was stored.
+    async fn save_holidays(&self, holidays: Vec<Holiday>, year: i32) -> AppResult<()> {
+        self.inner.save_holidays(holidays, year).await?;
+        self.reload_year(year).await;
+        Ok(())
+    }
+
+    // Answers from the index; years that are not loaded yet are read through and cached.
+    async fn get_holidays_by_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+        if let Some(holidays) = self.read_index(|index| index.by_year.get(&year).cloned()) {
+            return Ok(holidays);
+        }
+
+        let holidays = self.inner.get_holidays_by_year(year).await?;
+        self.write_index(|index| index.insert_year(year, holidays.clone()));
+        Ok(holidays)
+    }
+
+    // Answers from the index, falling back to the wrapped repository for years that are not loaded.
+    async fn holidays_exist_for_year(&self, year: i32) -> AppResult<bool> {
+        if self.read_index(|index| index.by_year.contains_key(&year)) {
+            return Ok(true);
+        }
+        self.inner.holidays_exist_for_year(year).await
+    }
+
+    // Lists the years held in the index, which mirrors the wrapped repository.
+    async fn list_available_years(&self) -> AppResult<Vec<i32>> {
+        Ok(self.read_index(|index| index.by_year.keys().copied().collect()))
+    }
+
+    // Answers from the date index without touching the wrapped repository.
+    async fn get_holidays_in_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<Holiday>> {
+        if from > to {
+            return Ok(Vec::new());
+        }
+        Ok(self.read_index(|index| {
+            index
+                .by_date
+                .range(from..=to)
+                .flat_map(|(_, holidays)| holidays.iter().cloned())
+                .collect()
+        }))
+    }
+
+    // Answers from the date index without touching the wrapped repository.
+    async fn find_by_date(&self, date: NaiveDate) -> AppResult<Vec<Holiday>> {
+        Ok(self.read_index(|index| index.by_date.get(&date).cloned().unwrap_or_default()))
+    }
+
+    // Deletes through the wrapped repository, then drops the year from the index.
+    async fn delete_year(&self, year: i32) -> AppResult<()> {
+        self.inner.delete_year(year).await?;
+        self.write_index(|index| index.remove_year(year));
+        Ok(())
+    }
+
+    // Updates through the wrapped repository, then reloads the year.
+    async fn update_year(&self, year: i32, update: HolidayUpdate) -> AppResult<()> {
+        self.inner.update_year(year, update).await?;
+        self.reload_year(year).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::domain::entities::api_key::ApiKey;
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::infrastructure::persistence::file_storage::{load_json, store_json};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+// FileApiKeyRepository stores all API keys in a single JSON file in the state directory.
+// Only the hashes of the secrets are written. The file is written atomically; a mutex serialises the
+// read-modify-write cycle of concurrent changes.
+pub struct FileApiKeyRepository {
+    file_path: String,
+    lock: Mutex<()>,
+}
+
+impl FileApiKeyRepository {
+    // Constructor to initialize FileApiKeyRepository; keys are stored in `{data_dir}/state/api_keys.json`.
+    pub fn new(data_dir: String) -> Self {
+        Self {
+            file_path: format!("{}/state/api_keys.json", data_dir),
+            lock: Mutex::new(()),
+        }
+    }
+
+    // Reads all keys from the file. A missing file means there are no keys yet.
+    async fn load_keys(&self) -> AppResult<Vec<ApiKey>> {
+        Ok(load_json(&self.file_path).await?.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for FileApiKeyRepository {
+    // Appends the key to the file.
+    async fn save_key(&self, api_key: ApiKey) -> AppResult<()> {
+        let _guard = self.lock.lock().await;
+        let mut keys = self.load_keys().await?;
+        keys.push(api_key);
+        store_json(&self.file_path, &keys).await
+    }
+
+    // Lists all keys, ordered by creation time.
+    async fn list_keys(&self) -> AppResult<Vec<ApiKey>> {
+        let mut keys = self.load_keys().await?;
+        keys.sort_by_key(|api_key| api_key.created_at);
+        Ok(keys)
+    }
+
+    // Finds the key with the given hash.
+    async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>> {
+        Ok(self
+            .load_keys()
+            .await?
+            .into_iter()
+            .find(|api_key| api_key.key_hash == key_hash))
+    }
+
+    // Removes the key with the given id from the file.
+    async fn delete_key(&self, id: &str) -> AppResult<()> {
+        let _guard = self.lock.lock().await;
+        let mut keys = self.load_keys().await?;
+        let count = keys.len();
+        keys.retain(|api_key| api_key.id != id);
+        if keys.len() == count {
+            return Err(AppError::NotFound(format!("API key {} not found", id)));
+        }
+        store_json(&self.file_path, &keys).await
+    }
+}
@@ -0,0 +1,149 @@
+use crate::domain::entities::scrape_job::{JobStatus, ScrapeJob};
+use crate::domain::repositories::scrape_job_repository::ScrapeJobRepository;
+use crate::infrastructure::persistence::file_storage::{load_json, update_json};
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use std::cmp::Reverse;
+
+// How many finished (succeeded or failed) jobs are kept; older ones are dropped when a job is saved.
+// Queued and running jobs are always kept.
+const MAX_FINISHED_JOBS: usize = 100;
+
+// FileScrapeJobRepository stores all scrape jobs in a single JSON file in the state directory.
+// Every change is a read-modify-write under the file's exclusive lock, so instances sharing DATA_DIR can claim
+// jobs from the same queue without two of them running the same job.
+pub struct FileScrapeJobRepository {
+    file_path: String,
+}
+
+impl FileScrapeJobRepository {
+    // Constructor to initialize FileScrapeJobRepository; jobs are stored in `{data_dir}/state/jobs.json`.
+    pub fn new(data_dir: String) -> Self {
+        Self {
+            file_path: format!("{}/state/jobs.json", data_dir),
+        }
+    }
+
+    // Reads all jobs from the file. A missing file means there are no jobs yet.
+    async fn load_jobs(&self) -> AppResult<Vec<ScrapeJob>> {
+        Ok(load_json(&self.file_path).await?.unwrap_or_default())
+    }
+
+    // Drops the oldest finished jobs beyond MAX_FINISHED_JOBS, so the file does not grow without bound.
+    fn prune_finished(jobs: &mut Vec<ScrapeJob>) {
+        let is_finished =
+            |job: &ScrapeJob| matches!(job.status, JobStatus::Succeeded | JobStatus::Failed);
+        let mut finished: Vec<_> = jobs
+            .iter()
+            .filter(|job| is_finished(job))
+            .map(|job| (job.finished_at.unwrap_or(job.created_at), job.id.clone()))
+            .collect();
+        if finished.len() <= MAX_FINISHED_JOBS {
+            return;
+        }
+
+        finished.sort_by_key(|(finished_at, _)| Reverse(*finished_at));
+        let dropped: Vec<String> = finished
+            .split_off(MAX_FINISHED_JOBS)
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect();
+        jobs.retain(|job| !dropped.contains(&job.id));
+    }
+}
+
+#[async_trait]
+impl ScrapeJobRepository for FileScrapeJobRepository {
+    // Inserts the job or replaces the stored job with the same id, pruning old finished jobs.
+    async fn save_job(&self, job: ScrapeJob) -> AppResult<()> {
+        update_json(&self.file_path, move |jobs: &mut Vec<ScrapeJob>| {
+            match jobs.iter_mut().find(|existing| existing.id == job.id) {
+                Some(existing) => *existing = job,
+                None => jobs.push(job),
+            }
+            Self::prune_finished(jobs);
+            Ok(())
+        })
+        .await
+    }
+
+    // Looks up a job by id.
+    async fn get_job(&self, id: &str) -> AppResult<Option<ScrapeJob>> {
+        Ok(self.load_jobs().await?.into_iter().find(|job| job.id == id))
+    }
+
+    // Marks the oldest queued job as running by the given instance.
+    async fn claim_next_job(
+        &self,
+        owner: &str,
+        now: NaiveDateTime,
+    ) -> AppResult<Option<ScrapeJob>> {
+        let owner = owner.to_string();
+        update_json(&self.file_path, move |jobs: &mut Vec<ScrapeJob>| {
+            let next = jobs
+                .iter_mut()
+                .filter(|job| job.status == JobStatus::Queued)
+                .min_by_key(|job| job.created_at);
+            Ok(next.map(|job| {
+                job.start(&owner, now);
+                job.clone()
+            }))
+        })
+        .await
+    }
+
+    // Requeues the running jobs of the given instance, and those without an owner.
+    async fn requeue_jobs_of(&self, owner: &str) -> AppResult<usize> {
+        let owner = owner.to_string();
+        update_json(&self.file_path, move |jobs: &mut Vec<ScrapeJob>| {
+            let mut count = 0;
+            for job in jobs.iter_mut().filter(|job| {
+                job.status == JobStatus::Running && job.owner.as_deref().is_none_or(|o| o == owner)
+            }) {
+                job.requeue();
+                count += 1;
+            }
+            Ok(count)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn jobs_are_claimed_once_and_only_the_owners_jobs_are_requeued() {
+        let dir = std::env::temp_dir().join(format!("scrape-jobs-{}", uuid::Uuid::new_v4()));
+        let repository = FileScrapeJobRepository::new(dir.to_string_lossy().into_owned());
+        for (id, hour) in [("second", 2), ("first", 1)] {
+            let job = ScrapeJob::new(id.to_string(), 2025, None, at(hour));
+            repository.save_job(job).await.unwrap();
+        }
+
+        let first = repository.claim_next_job("a", at(3)).await.unwrap();
+        let second = repository.claim_next_job("b", at(3)).await.unwrap();
+        let none = repository.claim_next_job("a", at(3)).await.unwrap();
+        let requeued = repository.requeue_jobs_of("a").await.unwrap();
+        let first_after = repository.get_job("first").await.unwrap().unwrap();
+        let second_after = repository.get_job("second").await.unwrap().unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(first.unwrap().id, "first");
+        assert_eq!(second.unwrap().owner.as_deref(), Some("b"));
+        assert!(none.is_none());
+        assert_eq!(requeued, 1);
+        assert_eq!(first_after.status, JobStatus::Queued);
+        assert_eq!(second_after.status, JobStatus::Running);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::domain::entities::scheduled_job::JobRun;
+use crate::domain::repositories::job_run_repository::JobRunRepository;
+use crate::infrastructure::persistence::file_storage::{load_json, update_json};
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+
+// How many runs are kept per job; older runs are dropped when a new one is recorded.
+const MAX_RUNS_PER_JOB: usize = 100;
+
+// FileJobRunRepository stores the run history of the scheduled jobs in a single JSON file in the state directory.
+// A run is recorded with a read-modify-write under the file's exclusive lock, so replicas sharing DATA_DIR keep
+// one history.
+pub struct FileJobRunRepository {
+    file_path: String,
+}
+
+impl FileJobRunRepository {
+    // Constructor to initialize FileJobRunRepository; runs are stored in `{data_dir}/state/scheduler_runs.json`.
+    pub fn new(data_dir: String) -> Self {
+        Self {
+            file_path: format!("{}/state/scheduler_runs.json", data_dir),
+        }
+    }
+
+    // Reads all runs from the file. A missing file means there are no runs yet.
+    async fn load_runs(&self) -> AppResult<Vec<JobRun>> {
+        Ok(load_json(&self.file_path).await?.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl JobRunRepository for FileJobRunRepository {
+    // Appends the run, keeping only the most recent runs of its job.
+    // The last successful run of the job is always kept, since the scheduler catches up from it.
+    async fn save_run(&self, run: JobRun) -> AppResult<()> {
+        update_json(&self.file_path, move |runs: &mut Vec<JobRun>| {
+            let job = run.job.clone();
+            runs.push(run);
+
+            let count = runs.iter().filter(|r| r.job == job).count();
+            if count > MAX_RUNS_PER_JOB {
+                let last_success = runs
+                    .iter()
+                    .filter(|r| r.job == job && r.status.is_successful())
+                    .max_by_key(|r| r.scheduled_for)
+                    .map(|r| r.id.clone());
+
+                let mut excess = count - MAX_RUNS_PER_JOB;
+                runs.retain(|r| {
+                    if excess > 0 && r.job == job && Some(&r.id) != last_success.as_ref() {
+                        excess -= 1;
+                        return false;
+                    }
+                    true
+                });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    // Lists the most recent runs, newest first.
+    async fn list_runs(&self, job: Option<&str>, limit: usize) -> AppResult<Vec<JobRun>> {
+        let mut runs: Vec<JobRun> = self
+            .load_runs()
+            .await?
+            .into_iter()
+            .filter(|run| job.is_none_or(|job| run.job == job))
+            .collect();
+        runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+        runs.truncate(limit);
+        Ok(runs)
+    }
+
+    // Finds the successful run of the job that was due last.
+    async fn last_successful_run(&self, job: &str) -> AppResult<Option<JobRun>> {
+        Ok(self
+            .load_runs()
+            .await?
+            .into_iter()
+            .filter(|run| run.job == job && run.status.is_successful())
+            .max_by_key(|run| run.scheduled_for))
+    }
+}
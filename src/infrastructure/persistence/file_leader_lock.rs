@@ -0,0 +1,76 @@
+use crate::domain::services::leader_election::LeaderElection;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::Path;
+use tokio::sync::Mutex;
+
+// FileLeaderLock elects the scheduler leader with an exclusive advisory lock on a file in the state directory.
+// Instances sharing the same DATA_DIR compete for the lock; the operating system releases it when the
+// leader's process exits or dies, so another instance takes over. The lock file holds the leader's process id.
+// The lock is only as reliable as the file system's locking: it holds on local disks (replicas on one host) and on
+// NFSv4 with working lock support, but not on NFSv3 without a lock manager, SMB/CIFS mounts, FUSE file systems or
+// object storage mounts, where two instances may both get the lock. Replicas on several hosts should use
+// STORAGE=postgres, which elects the leader with a database advisory lock instead.
+pub struct FileLeaderLock {
+    lock_path: String,
+    held: Mutex<Option<File>>,
+}
+
+impl FileLeaderLock {
+    // Constructor to initialize FileLeaderLock; the lock is taken on `{data_dir}/state/scheduler.lock`.
+    pub fn new(data_dir: String) -> Self {
+        Self {
+            lock_path: format!("{}/state/scheduler.lock", data_dir),
+            held: Mutex::new(None),
+        }
+    }
+
+    // Opens the lock file and tries to lock it without waiting.
+    // Returns None if another process holds the lock.
+    fn try_lock(&self) -> AppResult<Option<File>> {
+        if let Some(parent) = Path::new(&self.lock_path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.lock_path)?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                file.set_len(0)?;
+                writeln!(file, "{}", std::process::id())?;
+                Ok(Some(file))
+            }
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElection for FileLeaderLock {
+    // Keeps the lock once taken. If the lock file was removed while held, the lock no longer excludes
+    // anyone, so it is dropped and taken again on a new file.
+    async fn try_acquire(&self) -> AppResult<bool> {
+        let mut held = self.held.lock().await;
+        if held.is_some() && Path::new(&self.lock_path).exists() {
+            return Ok(true);
+        }
+
+        *held = self.try_lock()?;
+        Ok(held.is_some())
+    }
+
+    // Unlocks the lock file by closing it.
+    async fn release(&self) -> AppResult<()> {
+        let mut held = self.held.lock().await;
+        if let Some(file) = held.take() {
+            file.unlock()?;
+        }
+        Ok(())
+    }
+}
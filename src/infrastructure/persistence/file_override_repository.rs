@@ -0,0 +1,81 @@
+use crate::domain::entities::holiday_override::HolidayOverride;
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
+use crate::infrastructure::persistence::file_storage::{load_json, store_json};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+// FileHolidayOverrideRepository stores all manual overrides in a single JSON file in the data directory,
+// next to (but separate from) the scraped `{year}.json` files, which scrapes overwrite.
+// The file is written atomically; a mutex serialises the read-modify-write cycle of concurrent changes.
+pub struct FileHolidayOverrideRepository {
+    file_path: String,
+    lock: Mutex<()>,
+}
+
+impl FileHolidayOverrideRepository {
+    // Constructor to initialize FileHolidayOverrideRepository; overrides are stored in `{data_dir}/overrides.json`.
+    pub fn new(data_dir: String) -> Self {
+        Self {
+            file_path: format!("{}/overrides.json", data_dir),
+            lock: Mutex::new(()),
+        }
+    }
+
+    // Reads all overrides from the file. A missing file means there are no overrides yet.
+    async fn load_overrides(&self) -> AppResult<Vec<HolidayOverride>> {
+        Ok(load_json(&self.file_path).await?.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl HolidayOverrideRepository for FileHolidayOverrideRepository {
+    // Appends the override to the file.
+    async fn save_override(&self, holiday_override: HolidayOverride) -> AppResult<()> {
+        let _guard = self.lock.lock().await;
+        let mut overrides = self.load_overrides().await?;
+        overrides.push(holiday_override);
+        store_json(&self.file_path, &overrides).await
+    }
+
+    // Lists the overrides of a year, ordered by creation time.
+    async fn list_overrides(&self, year: i32) -> AppResult<Vec<HolidayOverride>> {
+        let mut overrides: Vec<HolidayOverride> = self
+            .load_overrides()
+            .await?
+            .into_iter()
+            .filter(|holiday_override| holiday_override.year == year)
+            .collect();
+        overrides.sort_by_key(|holiday_override| holiday_override.created_at);
+        Ok(overrides)
+    }
+
+    // Lists the distinct years of all overrides.
+    async fn list_override_years(&self) -> AppResult<Vec<i32>> {
+        let mut years: Vec<i32> = self
+            .load_overrides()
+            .await?
+            .iter()
+            .map(|holiday_override| holiday_override.year)
+            .collect();
+        years.sort();
+        years.dedup();
+        Ok(years)
+    }
+
+    // Removes the override with the given id from the file, if it belongs to the year.
+    async fn delete_override(&self, year: i32, id: &str) -> AppResult<()> {
+        let _guard = self.lock.lock().await;
+        let mut overrides = self.load_overrides().await?;
+        let count = overrides.len();
+        overrides
+            .retain(|holiday_override| holiday_override.id != id || holiday_override.year != year);
+        if overrides.len() == count {
+            return Err(AppError::NotFound(format!(
+                "Override {} not found for year {}",
+                id, year
+            )));
+        }
+        store_json(&self.file_path, &overrides).await
+    }
+}
@@ -1,20 +1,61 @@
 use crate::domain::entities::holiday::{Holiday, HolidayRaw};
-use crate::domain::repositories::holiday_repository::HolidayRepository;
+use crate::domain::repositories::holiday_repository::{HolidayRepository, HolidayUpdate};
+use crate::infrastructure::persistence::file_storage::{
+    lock_exclusive, lock_path, read_locked, remove_locked, replace_locked, run_blocking,
+    write_atomically,
+};
 use crate::shared::errors::app_error::{AppError, AppResult};
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use std::fs;
 use std::path::Path;
+use tokio::sync::Mutex;
 
 // FileHolidayRepository struct stores the directory path where holiday data files are located.
+// Each year is stored in `{year}.json`, with the previous version kept in `{year}.json.bak`
+// and an advisory lock file `{year}.json.lock` coordinating multiple processes.
+// Every write takes `write_lock`, so writes within the process are serialised; updates keep it,
+// together with the year's exclusive file lock, across their whole read-modify-write.
+// The trait methods take the lock, so they never call each other while holding it.
 pub struct FileHolidayRepository {
     data_dir: String,
+    write_lock: Mutex<()>,
 }
 
 impl FileHolidayRepository {
     // Constructor to initialize FileHolidayRepository with a specific data directory.
     pub fn new(data_dir: String) -> Self {
-        Self { data_dir }
+        Self {
+            data_dir,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    // Lists the years that have a `{year}.json` file in the data directory, in ascending order.
+    // Backup, lock and temporary files as well as non-year JSON files are ignored.
+    pub async fn stored_years(&self) -> AppResult<Vec<i32>> {
+        let data_dir = self.data_dir.clone();
+        run_blocking(move || {
+            if !Path::new(&data_dir).exists() {
+                return Ok(Vec::new());
+            }
+
+            let mut years: Vec<i32> = fs::read_dir(&data_dir)?
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| Self::year_from_path(&entry.path()))
+                .collect();
+            years.sort();
+            Ok(years)
+        })
+        .await
+    }
+
+    // Extracts the year from a data file path like "data/2025.json".
+    pub fn year_from_path(path: &Path) -> Option<i32> {
+        if path.extension()? != "json" {
+            return None;
+        }
+        path.file_stem()?.to_str()?.parse().ok()
     }
 
     // Returns the file path for the given year, constructing it using the data directory and year.
@@ -22,14 +63,8 @@ impl FileHolidayRepository {
         format!("{}/{}.json", self.data_dir, year)
     }
 
-    // Ensures the data directory exists by creating it if necessary.
-    fn ensure_data_dir_exists(&self) -> AppResult<()> {
-        fs::create_dir_all(&self.data_dir)?;
-        Ok(())
-    }
-
     // Converts a raw holiday record to a domain-specific holiday entity.
-    // It parses the date from string format and checks if it contains "Cuti Bersama".
+    // It parses the date from string format, and checks if it contains "Cuti Bersama".
     fn convert_raw_to_domain(&self, raw: HolidayRaw) -> AppResult<Holiday> {
         let date = NaiveDate::parse_from_str(&raw.tanggal, "%Y-%m-%d")
             .map_err(|e| AppError::BadRequest(format!("Invalid date format: {}", e)))?;
@@ -46,27 +81,61 @@ impl FileHolidayRepository {
             keterangan: holiday.description,
         }
     }
-}
 
-#[async_trait]
-impl HolidayRepository for FileHolidayRepository {
-    // Asynchronously saves a list of holidays to a file corresponding to the given year.
-    // It ensures the data directory exists, converts holidays to raw format, and writes to JSON.
-    async fn save_holidays(&self, holidays: Vec<Holiday>, year: i32) -> AppResult<()> {
-        self.ensure_data_dir_exists()?;
+    // Parses the JSON content of a data file into domain holidays.
+    fn parse_holidays(&self, contents: &str) -> AppResult<Vec<Holiday>> {
+        let raw_holidays: Vec<HolidayRaw> = serde_json::from_str(contents)?;
+
+        // Convert raw holidays into domain-specific holiday entities
+        raw_holidays
+            .into_iter()
+            .map(|raw| self.convert_raw_to_domain(raw))
+            .collect()
+    }
 
-        // Convert each holiday into its raw data format
+    // Serializes holidays to the pretty JSON stored in a data file.
+    fn to_json(&self, holidays: Vec<Holiday>) -> AppResult<String> {
         let raw_holidays: Vec<HolidayRaw> = holidays
             .into_iter()
             .map(|h| self.convert_domain_to_raw(h))
             .collect();
+        Ok(serde_json::to_string_pretty(&raw_holidays)?)
+    }
+
+    // Reads the holidays of a year while the caller holds the year's exclusive lock, so no lock is taken here.
+    // A year without a data file has no holidays; an unparseable file falls back to its backup.
+    async fn read_year_unlocked(&self, file_path: &str) -> AppResult<Vec<Holiday>> {
+        let contents = match tokio::fs::read_to_string(file_path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        match self.parse_holidays(&contents) {
+            Ok(holidays) => Ok(holidays),
+            Err(e) => match tokio::fs::read_to_string(format!("{}.bak", file_path)).await {
+                Ok(backup) => self.parse_holidays(&backup).map_err(|_| e),
+                Err(_) => Err(e),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl HolidayRepository for FileHolidayRepository {
+    // Asynchronously saves a list of holidays to a file corresponding to the given year.
+    // It converts holidays to raw format and writes the JSON atomically, keeping the previous version as a backup.
+    async fn save_holidays(&self, holidays: Vec<Holiday>, year: i32) -> AppResult<()> {
+        let _guard = self.write_lock.lock().await;
 
         // Prepare the file path and serialize the holidays into pretty JSON format
         let file_path = self.get_file_path(year);
-        let json_data = serde_json::to_string_pretty(&raw_holidays)?;
+        let json_data = self.to_json(holidays)?;
 
-        // Write the JSON data to a file
-        fs::write(&file_path, json_data)?;
+        // Write the JSON data to the file without blocking the async runtime
+        let data_dir = self.data_dir.clone();
+        let target_path = file_path.clone();
+        run_blocking(move || write_atomically(&data_dir, &target_path, &json_data)).await?;
         println!("File {} successfully created", file_path);
 
         Ok(())
@@ -74,31 +143,157 @@ impl HolidayRepository for FileHolidayRepository {
 
     // Asynchronously retrieves holidays from a file based on the given year.
     // If the file does not exist, it returns a NotFound error.
+    // If the file cannot be parsed, the backup of the previous version is used instead.
     async fn get_holidays_by_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
         let file_path = self.get_file_path(year);
 
-        if !Path::new(&file_path).exists() {
+        let path = file_path.clone();
+        let Some(contents) = run_blocking(move || read_locked(&path, &lock_path(&path))).await?
+        else {
             return Err(AppError::NotFound(format!(
                 "Holiday data not found for year {}",
                 year
             )));
+        };
+
+        match self.parse_holidays(&contents) {
+            Ok(holidays) => Ok(holidays),
+            Err(e) => {
+                let path = file_path.clone();
+                let backup =
+                    run_blocking(move || read_locked(&format!("{}.bak", path), &lock_path(&path)))
+                        .await?;
+                match backup.map(|contents| self.parse_holidays(&contents)) {
+                    Some(Ok(holidays)) => {
+                        eprintln!(
+                            "⚠️ {} is unreadable ({}), serving its backup instead",
+                            file_path, e
+                        );
+                        Ok(holidays)
+                    }
+                    _ => Err(e),
+                }
+            }
+        }
+    }
+
+    // Asynchronously checks if holiday data exists for the given year by checking if the file exists.
+    async fn holidays_exist_for_year(&self, year: i32) -> AppResult<bool> {
+        let file_path = self.get_file_path(year);
+        Ok(tokio::fs::try_exists(&file_path).await?)
+    }
+
+    // Lists the years that have a data file, in ascending order.
+    async fn list_available_years(&self) -> AppResult<Vec<i32>> {
+        self.stored_years().await
+    }
+
+    // Reads the data file of every year the range touches and keeps the holidays inside the range.
+    async fn get_holidays_in_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<Holiday>> {
+        let mut holidays = Vec::new();
+        for year in from.year()..=to.year() {
+            match self.get_holidays_by_year(year).await {
+                Ok(year_holidays) => holidays.extend(
+                    year_holidays
+                        .into_iter()
+                        .filter(|holiday| holiday.date >= from && holiday.date <= to),
+                ),
+                Err(AppError::NotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
         }
 
-        let contents = fs::read_to_string(&file_path)?;
-        let raw_holidays: Vec<HolidayRaw> = serde_json::from_str(&contents)?;
+        holidays.sort_by_key(|holiday| holiday.date);
+        Ok(holidays)
+    }
 
-        // Convert raw holidays into domain-specific holiday entities
-        let holidays: Result<Vec<Holiday>, AppError> = raw_holidays
-            .into_iter()
-            .map(|raw| self.convert_raw_to_domain(raw))
-            .collect();
+    // Reads the data file of the date's year and keeps the holidays on that date.
+    async fn find_by_date(&self, date: NaiveDate) -> AppResult<Vec<Holiday>> {
+        self.get_holidays_in_range(date, date).await
+    }
+
+    // Moves the year's data file to its backup.
+    async fn delete_year(&self, year: i32) -> AppResult<()> {
+        let _guard = self.write_lock.lock().await;
+
+        let file_path = self.get_file_path(year);
+        let path = file_path.clone();
+        if !run_blocking(move || remove_locked(&path)).await? {
+            return Err(AppError::NotFound(format!(
+                "Holiday data not found for year {}",
+                year
+            )));
+        }
+        println!("File {} successfully deleted", file_path);
 
-        holidays
+        Ok(())
     }
 
-    // Asynchronously checks if holiday data exists for the given year by checking if the file exists.
-    async fn holidays_exist_for_year(&self, year: i32) -> AppResult<bool> {
+    // Reads, changes and rewrites the year's file while holding `write_lock` and the year's exclusive file lock,
+    // so neither this process nor another one can write the year in between.
+    async fn update_year(&self, year: i32, update: HolidayUpdate) -> AppResult<()> {
+        let _guard = self.write_lock.lock().await;
+
         let file_path = self.get_file_path(year);
-        Ok(Path::new(&file_path).exists())
+        let data_dir = self.data_dir.clone();
+        let path = file_path.clone();
+        let lock = run_blocking(move || lock_exclusive(&data_dir, &path)).await?;
+
+        let holidays = update(self.read_year_unlocked(&file_path).await?);
+        let json_data = self.to_json(holidays)?;
+
+        let data_dir = self.data_dir.clone();
+        let path = file_path.clone();
+        run_blocking(move || {
+            replace_locked(&data_dir, &path, &json_data)?;
+            lock.unlock()?;
+            Ok(())
+        })
+        .await?;
+        println!("File {} successfully updated", file_path);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn concurrent_updates_of_a_year_are_all_kept() {
+        let data_dir = std::env::temp_dir().join(format!("holidays-{}", uuid::Uuid::new_v4()));
+        let repository = Arc::new(FileHolidayRepository::new(
+            data_dir.to_string_lossy().into_owned(),
+        ));
+
+        let mut tasks = Vec::new();
+        for day in 1..=20 {
+            let repository = repository.clone();
+            tasks.push(tokio::spawn(async move {
+                let date = NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+                repository
+                    .update_year(
+                        2025,
+                        Box::new(move |mut holidays| {
+                            holidays.push(Holiday::new(date, format!("Day {}", day), false));
+                            holidays
+                        }),
+                    )
+                    .await
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let stored = repository.get_holidays_by_year(2025).await;
+        let _ = std::fs::remove_dir_all(&data_dir);
+        assert_eq!(stored.unwrap().len(), 20);
     }
 }
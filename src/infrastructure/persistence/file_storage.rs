@@ -0,0 +1,203 @@
+use crate::shared::errors::app_error::{AppError, AppResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+// Runs blocking file system work on tokio's blocking thread pool, so it does not stall the async runtime.
+pub async fn run_blocking<T, F>(work: F) -> AppResult<T>
+where
+    F: FnOnce() -> AppResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| AppError::InternalServer(format!("Blocking task failed: {}", e)))?
+}
+
+// Opens (creating if necessary) the lock file guarding a data file.
+// The lock lives in a separate file because the data file itself is replaced on every save.
+pub fn open_lock_file(lock_path: &str) -> AppResult<File> {
+    Ok(OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?)
+}
+
+// Returns the path of the lock file guarding a data file.
+pub fn lock_path(file_path: &str) -> String {
+    format!("{}.lock", file_path)
+}
+
+// Takes the exclusive advisory lock guarding a data file, creating the data directory if necessary.
+// Like the leader lock, it relies on the file system's locking, which network file systems may not provide reliably.
+// The lock is held until the returned file is dropped, so a read-modify-write can span several steps.
+pub fn lock_exclusive(data_dir: &str, file_path: &str) -> AppResult<File> {
+    fs::create_dir_all(data_dir)?;
+
+    let lock = open_lock_file(&lock_path(file_path))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+// Writes the data file crash-safely while holding an exclusive advisory lock (see replace_locked).
+pub fn write_atomically(data_dir: &str, file_path: &str, json_data: &str) -> AppResult<()> {
+    let lock = lock_exclusive(data_dir, file_path)?;
+    replace_locked(data_dir, file_path, json_data)?;
+    lock.unlock()?;
+    Ok(())
+}
+
+// Replaces the data file crash-safely; the caller must hold its exclusive lock (see lock_exclusive):
+// the new content is written and synced to a temporary file, the current file is copied to the backup,
+// and the temporary file is then atomically renamed over the data file.
+// A crash at any point leaves either the old or the new file in place, never a truncated one.
+pub fn replace_locked(data_dir: &str, file_path: &str, json_data: &str) -> AppResult<()> {
+    let temp_path = format!("{}.tmp", file_path);
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(json_data.as_bytes())?;
+    temp_file.sync_all()?;
+
+    if Path::new(file_path).exists() {
+        fs::copy(file_path, format!("{}.bak", file_path))?;
+    }
+    fs::rename(&temp_path, file_path)?;
+
+    // Persist the rename itself by syncing the directory entry (not supported on every platform).
+    if let Ok(dir) = File::open(data_dir) {
+        let _ = dir.sync_all();
+    }
+
+    Ok(())
+}
+
+// Deletes the data file while holding an exclusive advisory lock.
+// The file is moved to the backup rather than removed, so deleted data can still be recovered by hand.
+// Returns false if the file does not exist.
+pub fn remove_locked(file_path: &str) -> AppResult<bool> {
+    if !Path::new(file_path).exists() {
+        return Ok(false);
+    }
+
+    let lock = open_lock_file(&lock_path(file_path))?;
+    lock.lock()?;
+    let removed = match fs::rename(file_path, format!("{}.bak", file_path)) {
+        Ok(()) => true,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+        Err(e) => return Err(e.into()),
+    };
+    lock.unlock()?;
+
+    Ok(removed)
+}
+
+// Opens the lock file guarding a data file for a shared lock.
+// A read-only DATA_DIR cannot get a new lock file, so an existing one is opened read-only instead.
+// Returns None if there is no lock file to open: nothing can write to the directory then,
+// so reading without a lock is safe.
+fn open_read_lock(lock_path: &str) -> Option<File> {
+    open_lock_file(lock_path)
+        .ok()
+        .or_else(|| File::open(lock_path).ok())
+}
+
+// Reads a data (or backup) file while holding a shared advisory lock on its lock file,
+// so a concurrent save from another process is never observed half-way.
+// Falls back to a lock-free read when the lock file cannot be opened, e.g. on a read-only DATA_DIR.
+// Returns None if the file does not exist.
+pub fn read_locked(file_path: &str, lock_path: &str) -> AppResult<Option<String>> {
+    if !Path::new(file_path).exists() {
+        return Ok(None);
+    }
+
+    let lock = open_read_lock(lock_path);
+    if let Some(lock) = &lock {
+        lock.lock_shared()?;
+    }
+    let contents = match fs::read_to_string(file_path) {
+        Ok(contents) => Some(contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(lock) = &lock {
+        lock.unlock()?;
+    }
+
+    Ok(contents)
+}
+
+// Reads and parses a JSON state file, e.g. `state/jobs.json`, without blocking the async runtime.
+// Returns None if the file does not exist.
+pub async fn load_json<T>(file_path: &str) -> AppResult<Option<T>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let path = file_path.to_string();
+    run_blocking(move || match read_locked(&path, &lock_path(&path))? {
+        Some(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+        None => Ok(None),
+    })
+    .await
+}
+
+// Writes a JSON state file atomically (see write_atomically), creating its directory if necessary.
+pub async fn store_json<T: Serialize>(file_path: &str, value: &T) -> AppResult<()> {
+    let json_data = serde_json::to_string_pretty(value)?;
+    let path = file_path.to_string();
+    let dir = Path::new(file_path)
+        .parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".".to_string());
+    run_blocking(move || write_atomically(&dir, &path, &json_data)).await
+}
+
+// Reads, changes and writes a JSON state file as one step under its exclusive lock, so concurrent updates, also
+// from other processes sharing DATA_DIR, never overwrite each other. A missing file starts from the default value.
+// The file is only written if the update succeeds; its result is returned.
+pub async fn update_json<T, R, F>(file_path: &str, update: F) -> AppResult<R>
+where
+    T: DeserializeOwned + Serialize + Default,
+    R: Send + 'static,
+    F: FnOnce(&mut T) -> AppResult<R> + Send + 'static,
+{
+    let path = file_path.to_string();
+    let dir = Path::new(file_path)
+        .parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".".to_string());
+    run_blocking(move || {
+        let lock = lock_exclusive(&dir, &path)?;
+        let mut value: T = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+            Err(e) => return Err(e.into()),
+        };
+        let result = update(&mut value)?;
+        replace_locked(&dir, &path, &serde_json::to_string_pretty(&value)?)?;
+        lock.unlock()?;
+        Ok(result)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_locked_reads_without_a_lock_when_the_lock_file_cannot_be_opened() {
+        let dir = std::env::temp_dir().join(format!("read-locked-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("2025.json").to_string_lossy().into_owned();
+        fs::write(&file_path, "[]").unwrap();
+        // Neither created nor opened, as in a read-only DATA_DIR without lock files.
+        let lock_path = dir.join("missing").join("2025.json.lock");
+
+        let contents = read_locked(&file_path, &lock_path.to_string_lossy());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(contents.unwrap().as_deref(), Some("[]"));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::infrastructure::persistence::cached_repository::CachedHolidayRepository;
+use crate::infrastructure::persistence::file_repository::FileHolidayRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use notify::{Event, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+// How long to wait for further changes before reloading, so a burst of writes
+// (temp file, backup, rename) results in a single reload per year.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+// Watches the data directory and reloads years in the cache when their JSON files change,
+// so edits made outside the API (or by another process) are served without a restart.
+pub fn watch_data_dir(data_dir: &str, cache: Arc<CachedHolidayRepository>) -> AppResult<()> {
+    std::fs::create_dir_all(data_dir)?;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel::<Event>();
+    let mut watcher =
+        notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            // Reads (including our own reloads) raise access events, which are ignored.
+            Ok(event) if !event.kind.is_access() => {
+                let _ = sender.send(event);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("File watcher error: {}", e),
+        })
+        .map_err(|e| AppError::InternalServer(format!("Failed to start file watcher: {}", e)))?;
+    watcher
+        .watch(Path::new(data_dir), RecursiveMode::NonRecursive)
+        .map_err(|e| AppError::InternalServer(format!("Failed to watch {}: {}", data_dir, e)))?;
+
+    println!("👀 Watching {} for holiday data changes", data_dir);
+
+    tokio::spawn(async move {
+        // The watcher stops when dropped, so the task keeps it alive.
+        let _watcher = watcher;
+
+        while let Some(event) = receiver.recv().await {
+            let mut years: BTreeSet<i32> = event
+                .paths
+                .iter()
+                .filter_map(|path| FileHolidayRepository::year_from_path(path))
+                .collect();
+
+            // Collect the rest of the burst before reloading.
+            sleep(DEBOUNCE).await;
+            while let Ok(event) = receiver.try_recv() {
+                years.extend(
+                    event
+                        .paths
+                        .iter()
+                        .filter_map(|path| FileHolidayRepository::year_from_path(path)),
+                );
+            }
+
+            for year in years {
+                let present = cache.reload_year(year).await;
+                println!(
+                    "🔄 Reloaded holidays for year {} after a file change{}",
+                    year,
+                    if present { "" } else { " (removed)" }
+                );
+            }
+        }
+    });
+
+    Ok(())
+}
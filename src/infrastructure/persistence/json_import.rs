@@ -0,0 +1,79 @@
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
+use crate::domain::repositories::holiday_repository::HolidayRepository;
+use crate::infrastructure::persistence::file_api_key_repository::FileApiKeyRepository;
+use crate::infrastructure::persistence::file_override_repository::FileHolidayOverrideRepository;
+use crate::infrastructure::persistence::file_repository::FileHolidayRepository;
+use crate::shared::errors::app_error::AppResult;
+
+// Imports the `{year}.json` files from a data directory into another repository.
+// Years that already exist in the target are left untouched, so the import can safely run on every start
+// and effectively happens only once per year.
+// Returns the number of years that were imported.
+pub async fn import_json_files(data_dir: &str, target: &dyn HolidayRepository) -> AppResult<usize> {
+    let source = FileHolidayRepository::new(data_dir.to_string());
+    let mut imported = 0;
+
+    for year in source.stored_years().await? {
+        if target.holidays_exist_for_year(year).await? {
+            continue;
+        }
+
+        match source.get_holidays_by_year(year).await {
+            Ok(holidays) => {
+                let count = holidays.len();
+                target.save_holidays(holidays, year).await?;
+                println!("Imported {} holidays for year {} from JSON", count, year);
+                imported += 1;
+            }
+            Err(e) => eprintln!("Skipping import of {}/{}.json: {}", data_dir, year, e),
+        }
+    }
+
+    Ok(imported)
+}
+
+// Imports the overrides in `overrides.json` from a data directory into another override repository.
+// The import only runs while the target has no overrides at all, so it happens once and never
+// brings back overrides that were withdrawn afterwards.
+// Returns the number of overrides that were imported.
+pub async fn import_override_file(
+    data_dir: &str,
+    target: &dyn HolidayOverrideRepository,
+) -> AppResult<usize> {
+    if !target.list_override_years().await?.is_empty() {
+        return Ok(0);
+    }
+
+    let source = FileHolidayOverrideRepository::new(data_dir.to_string());
+    let mut imported = 0;
+    for year in source.list_override_years().await? {
+        for holiday_override in source.list_overrides(year).await? {
+            target.save_override(holiday_override).await?;
+            imported += 1;
+        }
+    }
+
+    Ok(imported)
+}
+
+// Imports the API keys in `state/api_keys.json` from a data directory into another API key repository.
+// Like the override import, it only runs while the target has no keys at all, so revoked keys never come back.
+// Returns the number of keys that were imported.
+pub async fn import_api_key_file(
+    data_dir: &str,
+    target: &dyn ApiKeyRepository,
+) -> AppResult<usize> {
+    if !target.list_keys().await?.is_empty() {
+        return Ok(0);
+    }
+
+    let source = FileApiKeyRepository::new(data_dir.to_string());
+    let mut imported = 0;
+    for api_key in source.list_keys().await? {
+        target.save_key(api_key).await?;
+        imported += 1;
+    }
+
+    Ok(imported)
+}
@@ -1 +1,23 @@
-pub mod file_repository;
\ No newline at end of file
+pub mod cached_api_key_repository;
+pub mod cached_repository;
+pub mod file_api_key_repository;
+pub mod file_job_repository;
+pub mod file_job_run_repository;
+pub mod file_leader_lock;
+pub mod file_override_repository;
+pub mod file_repository;
+pub mod file_storage;
+pub mod file_watcher;
+pub mod json_import;
+pub mod postgres_api_key_repository;
+pub mod postgres_job_run_repository;
+pub mod postgres_leader_lock;
+pub mod postgres_override_repository;
+pub mod postgres_repository;
+pub mod postgres_scrape_job_repository;
+pub mod repository_factory;
+pub mod sqlite_api_key_repository;
+pub mod sqlite_job_run_repository;
+pub mod sqlite_override_repository;
+pub mod sqlite_repository;
+pub mod sqlite_scrape_job_repository;
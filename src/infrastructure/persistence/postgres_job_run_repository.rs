@@ -0,0 +1,104 @@
+use crate::domain::entities::scheduled_job::{JobRun, JobRunStatus};
+use crate::domain::repositories::job_run_repository::JobRunRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::Row;
+
+// How many runs are kept per job; older runs are deleted when a new one is recorded.
+const MAX_RUNS_PER_JOB: i64 = 100;
+
+// The columns of a `job_runs` row, in the order run_from_row reads them.
+const RUN_COLUMNS: &str = "id, job, scheduled_for, started_at, finished_at, status, message";
+
+// PostgresJobRunRepository stores the run history of the scheduled jobs in the `job_runs` table of the PostgreSQL
+// database, so every replica catches up from and reports the same runs. It shares the pool of
+// PostgresHolidayRepository, which creates the table in its migrations.
+pub struct PostgresJobRunRepository {
+    pool: PgPool,
+}
+
+impl PostgresJobRunRepository {
+    // Constructor to initialize PostgresJobRunRepository with a migrated connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Maps a `job_runs` row to a domain job run.
+fn run_from_row(row: &PgRow) -> AppResult<JobRun> {
+    let status: String = row.try_get("status")?;
+    Ok(JobRun {
+        id: row.try_get("id")?,
+        job: row.try_get("job")?,
+        scheduled_for: row.try_get("scheduled_for")?,
+        started_at: row.try_get("started_at")?,
+        finished_at: row.try_get("finished_at")?,
+        status: JobRunStatus::parse(&status).ok_or_else(|| {
+            AppError::InternalServer(format!("Unknown job run status '{}'", status))
+        })?,
+        message: row.try_get("message")?,
+    })
+}
+
+#[async_trait]
+impl JobRunRepository for PostgresJobRunRepository {
+    // Inserts the run, keeping only the most recent runs of its job.
+    // The last successful run of the job is always kept, since the scheduler catches up from it.
+    async fn save_run(&self, run: JobRun) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!(
+            "INSERT INTO job_runs ({}) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            RUN_COLUMNS
+        ))
+        .bind(&run.id)
+        .bind(&run.job)
+        .bind(run.scheduled_for)
+        .bind(run.started_at)
+        .bind(run.finished_at)
+        .bind(run.status.as_str())
+        .bind(&run.message)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "DELETE FROM job_runs WHERE job = $1
+               AND id NOT IN (SELECT id FROM job_runs WHERE job = $1 ORDER BY started_at DESC LIMIT $2)
+               AND id NOT IN (
+                   SELECT id FROM job_runs WHERE job = $1 AND status IN ('succeeded', 'skipped')
+                   ORDER BY scheduled_for DESC LIMIT 1
+               )",
+        )
+        .bind(&run.job)
+        .bind(MAX_RUNS_PER_JOB)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Lists the most recent runs, newest first.
+    async fn list_runs(&self, job: Option<&str>, limit: usize) -> AppResult<Vec<JobRun>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM job_runs WHERE $1::TEXT IS NULL OR job = $1 ORDER BY started_at DESC LIMIT $2",
+            RUN_COLUMNS
+        ))
+        .bind(job)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(run_from_row).collect()
+    }
+
+    // Finds the successful run of the job that was due last.
+    async fn last_successful_run(&self, job: &str) -> AppResult<Option<JobRun>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM job_runs WHERE job = $1 AND status IN ('succeeded', 'skipped')
+             ORDER BY scheduled_for DESC LIMIT 1",
+            RUN_COLUMNS
+        ))
+        .bind(job)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(run_from_row).transpose()
+    }
+}
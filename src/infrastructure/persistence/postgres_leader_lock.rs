@@ -0,0 +1,68 @@
+use crate::domain::services::leader_election::LeaderElection;
+use crate::shared::errors::app_error::AppResult;
+use async_trait::async_trait;
+use sqlx::postgres::PgConnection;
+use sqlx::Connection;
+use tokio::sync::Mutex;
+
+// Key of the advisory lock held by the scheduler leader.
+const LEADER_LOCK_KEY: i64 = 0x7363_6865_6475_6c65; // "schedule"
+
+// PostgresLeaderLock elects the scheduler leader with a session-level advisory lock in the shared database.
+// The lock is held on a dedicated connection: PostgreSQL releases it when that connection closes,
+// so if the leader dies or loses its connection, another replica takes over.
+pub struct PostgresLeaderLock {
+    database_url: String,
+    connection: Mutex<Option<PgConnection>>,
+}
+
+impl PostgresLeaderLock {
+    // Constructor to initialize PostgresLeaderLock; it connects lazily on the first election round.
+    pub fn new(database_url: &str) -> Self {
+        Self {
+            database_url: database_url.to_string(),
+            connection: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElection for PostgresLeaderLock {
+    // While the lock is held, checks that its connection is still alive; a broken connection means
+    // the database already released the lock, so it is dropped and the lock is competed for again.
+    async fn try_acquire(&self) -> AppResult<bool> {
+        let mut connection = self.connection.lock().await;
+        if let Some(held) = connection.as_mut() {
+            if held.ping().await.is_ok() {
+                return Ok(true);
+            }
+            *connection = None;
+        }
+
+        let mut candidate = PgConnection::connect(&self.database_url).await?;
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(LEADER_LOCK_KEY)
+            .fetch_one(&mut candidate)
+            .await?;
+
+        if acquired {
+            *connection = Some(candidate);
+        } else {
+            candidate.close().await?;
+        }
+        Ok(acquired)
+    }
+
+    // Unlocks the advisory lock and closes its connection.
+    async fn release(&self) -> AppResult<()> {
+        let Some(mut held) = self.connection.lock().await.take() else {
+            return Ok(());
+        };
+        sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(LEADER_LOCK_KEY)
+            .execute(&mut held)
+            .await?;
+        held.close().await?;
+        Ok(())
+    }
+}
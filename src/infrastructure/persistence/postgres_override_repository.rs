@@ -0,0 +1,97 @@
+use crate::domain::entities::holiday_override::{HolidayOverride, OverrideAction};
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::Row;
+
+// PostgresHolidayOverrideRepository stores manual overrides in the `holiday_overrides` table of the PostgreSQL database,
+// next to (but separate from) the scraped holidays. It shares the pool of PostgresHolidayRepository,
+// which creates the table in its migrations.
+pub struct PostgresHolidayOverrideRepository {
+    pool: PgPool,
+}
+
+impl PostgresHolidayOverrideRepository {
+    // Constructor to initialize PostgresHolidayOverrideRepository with a migrated connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Maps a `holiday_overrides` row to a domain override.
+fn override_from_row(row: &PgRow) -> AppResult<HolidayOverride> {
+    let action: String = row.try_get("action")?;
+    Ok(HolidayOverride {
+        id: row.try_get("id")?,
+        year: row.try_get("year")?,
+        date: row.try_get("date")?,
+        action: OverrideAction::parse(&action).ok_or_else(|| {
+            AppError::InternalServer(format!("Unknown override action '{}'", action))
+        })?,
+        match_description: row.try_get("match_description")?,
+        description: row.try_get("description")?,
+        is_joint_leave: row.try_get("is_joint_leave")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+#[async_trait]
+impl HolidayOverrideRepository for PostgresHolidayOverrideRepository {
+    // Inserts the override.
+    async fn save_override(&self, holiday_override: HolidayOverride) -> AppResult<()> {
+        sqlx::query(
+            "INSERT INTO holiday_overrides (id, year, date, action, match_description, description, is_joint_leave, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(&holiday_override.id)
+        .bind(holiday_override.year)
+        .bind(holiday_override.date)
+        .bind(holiday_override.action.as_str())
+        .bind(&holiday_override.match_description)
+        .bind(&holiday_override.description)
+        .bind(holiday_override.is_joint_leave)
+        .bind(holiday_override.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // Lists the overrides of a year, ordered by creation time.
+    async fn list_overrides(&self, year: i32) -> AppResult<Vec<HolidayOverride>> {
+        let rows = sqlx::query(
+            "SELECT id, year, date, action, match_description, description, is_joint_leave, created_at
+             FROM holiday_overrides WHERE year = $1 ORDER BY created_at, id",
+        )
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(override_from_row).collect()
+    }
+
+    // Lists the distinct years of all overrides.
+    async fn list_override_years(&self) -> AppResult<Vec<i32>> {
+        let years: Vec<i32> =
+            sqlx::query_scalar("SELECT DISTINCT year FROM holiday_overrides ORDER BY year")
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(years)
+    }
+
+    // Deletes the override with the given id, if it belongs to the year.
+    async fn delete_override(&self, year: i32, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM holiday_overrides WHERE id = $1 AND year = $2")
+            .bind(id)
+            .bind(year)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Override {} not found for year {}",
+                id, year
+            )));
+        }
+        Ok(())
+    }
+}
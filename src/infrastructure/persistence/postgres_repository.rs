@@ -0,0 +1,350 @@
+use crate::domain::entities::holiday::Holiday;
+use crate::domain::repositories::holiday_repository::{HolidayRepository, HolidayUpdate};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::now_in;
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use sqlx::postgres::{PgConnection, PgPool, PgPoolOptions, PgRow};
+use sqlx::Row;
+use std::time::Duration;
+
+// Key of the advisory lock taken while migrating, so replicas starting at the same time
+// do not apply the same migration twice.
+const MIGRATION_LOCK_KEY: i64 = 0x686f_6c69_6461_7973; // "holidays"
+
+// Schema migrations, applied in order. Each entry is (version, SQL).
+// Applied versions are recorded in `schema_migrations`, so new migrations are only ever appended.
+const MIGRATIONS: &[(i64, &str)] = &[
+    (
+        1,
+        "CREATE TABLE holiday_years (
+        year INTEGER PRIMARY KEY,
+        saved_at TIMESTAMP NOT NULL
+    );
+    CREATE TABLE holidays (
+        id BIGSERIAL PRIMARY KEY,
+        year INTEGER NOT NULL REFERENCES holiday_years(year) ON DELETE CASCADE,
+        date DATE NOT NULL,
+        description TEXT NOT NULL,
+        is_joint_leave BOOLEAN NOT NULL
+    );
+    CREATE INDEX idx_holidays_year ON holidays(year);
+    CREATE INDEX idx_holidays_date ON holidays(date);",
+    ),
+    (
+        2,
+        "ALTER TABLE holidays ADD COLUMN is_manual BOOLEAN NOT NULL DEFAULT FALSE;",
+    ),
+    (
+        3,
+        "CREATE TABLE holiday_overrides (
+        id TEXT PRIMARY KEY,
+        year INTEGER NOT NULL,
+        date DATE NOT NULL,
+        action TEXT NOT NULL,
+        match_description TEXT,
+        description TEXT,
+        is_joint_leave BOOLEAN,
+        created_at TIMESTAMP NOT NULL
+    );
+    CREATE INDEX idx_holiday_overrides_year ON holiday_overrides(year);",
+    ),
+    (
+        4,
+        "INSERT INTO holiday_overrides (id, year, date, action, match_description, description, is_joint_leave, created_at)
+        SELECT 'curated-' || id::TEXT, year, date, 'add', NULL, description, is_joint_leave, LOCALTIMESTAMP
+        FROM holidays WHERE is_manual = TRUE;
+    DELETE FROM holidays WHERE is_manual = TRUE;
+    ALTER TABLE holidays DROP COLUMN is_manual;",
+    ),
+    (
+        5,
+        "CREATE TABLE api_keys (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        key_hash TEXT NOT NULL UNIQUE,
+        scopes TEXT NOT NULL,
+        created_at TIMESTAMP NOT NULL
+    );",
+    ),
+    (
+        6,
+        "CREATE TABLE scrape_jobs (
+        id TEXT PRIMARY KEY,
+        year INTEGER NOT NULL,
+        strategy TEXT,
+        status TEXT NOT NULL,
+        owner TEXT,
+        created_at TIMESTAMP NOT NULL,
+        started_at TIMESTAMP,
+        finished_at TIMESTAMP,
+        holidays_scraped BIGINT,
+        error TEXT,
+        conflicts TEXT NOT NULL
+    );
+    CREATE INDEX idx_scrape_jobs_status ON scrape_jobs(status, created_at);",
+    ),    (
+        7,
+        "CREATE TABLE job_runs (
+        id TEXT PRIMARY KEY,
+        job TEXT NOT NULL,
+        scheduled_for TIMESTAMP NOT NULL,
+        started_at TIMESTAMP NOT NULL,
+        finished_at TIMESTAMP NOT NULL,
+        status TEXT NOT NULL,
+        message TEXT NOT NULL
+    );
+    CREATE INDEX idx_job_runs_job ON job_runs(job, started_at);",
+    ),
+];
+
+// PostgresHolidayRepository stores holidays in a PostgreSQL database shared by all API replicas.
+// Saves are transactional, and concurrent saves of the same year are serialised by the row lock
+// on `holiday_years`, so replicas never interleave their writes.
+// Its timestamps (`saved_at`, `applied_at`) are recorded in the service time zone.
+pub struct PostgresHolidayRepository {
+    pool: PgPool,
+    timezone: Tz,
+}
+
+impl PostgresHolidayRepository {
+    // Connects a pool to the database and applies pending migrations.
+    // Timestamps are recorded in the given service time zone.
+    pub async fn new(database_url: &str, max_connections: u32, timezone: Tz) -> AppResult<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections.max(1))
+            .acquire_timeout(Duration::from_secs(10))
+            .connect(database_url)
+            .await?;
+
+        let repository = Self { pool, timezone };
+        repository.run_migrations().await?;
+        Ok(repository)
+    }
+
+    // Returns the connection pool, so the other repositories of this backend share it.
+    pub fn pool(&self) -> PgPool {
+        self.pool.clone()
+    }
+
+    // Applies every migration that has not been recorded in `schema_migrations` yet.
+    // PostgreSQL DDL is transactional, so all pending migrations run in one transaction
+    // that holds an advisory lock; a failing migration leaves the schema untouched.
+    async fn run_migrations(&self) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(MIGRATION_LOCK_KEY)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version BIGINT PRIMARY KEY,
+                applied_at TIMESTAMP NOT NULL
+            )",
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (version, sql) in MIGRATIONS {
+            let applied: Option<i64> =
+                sqlx::query_scalar("SELECT version FROM schema_migrations WHERE version = $1")
+                    .bind(version)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if applied.is_some() {
+                continue;
+            }
+
+            sqlx::raw_sql(sql).execute(&mut *tx).await?;
+            sqlx::query("INSERT INTO schema_migrations (version, applied_at) VALUES ($1, $2)")
+                .bind(version)
+                .bind(now_in(self.timezone))
+                .execute(&mut *tx)
+                .await?;
+
+            println!("Applied PostgreSQL schema migration {}", version);
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+// Maps a `holidays` row (date, description, is_joint_leave) to a domain holiday.
+fn holiday_from_row(row: &PgRow) -> AppResult<Holiday> {
+    Ok(Holiday::new(
+        row.try_get::<NaiveDate, _>("date")?,
+        row.try_get("description")?,
+        row.try_get("is_joint_leave")?,
+    ))
+}
+
+// Creates the year, or refreshes its `saved_at` to the given time. As the first write of a transaction it locks the year,
+// so concurrent saves and updates of the year are serialised.
+async fn touch_year(conn: &mut PgConnection, year: i32, saved_at: NaiveDateTime) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO holiday_years (year, saved_at) VALUES ($1, $2)
+         ON CONFLICT (year) DO UPDATE SET saved_at = EXCLUDED.saved_at",
+    )
+    .bind(year)
+    .bind(saved_at)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+// Replaces the holidays of a year within the caller's transaction, after touch_year has locked the year.
+async fn replace_year(conn: &mut PgConnection, year: i32, holidays: &[Holiday]) -> AppResult<()> {
+    sqlx::query("DELETE FROM holidays WHERE year = $1")
+        .bind(year)
+        .execute(&mut *conn)
+        .await?;
+
+    for holiday in holidays {
+        sqlx::query(
+            "INSERT INTO holidays (year, date, description, is_joint_leave) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(year)
+        .bind(holiday.date)
+        .bind(&holiday.description)
+        .bind(holiday.is_joint_leave)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl HolidayRepository for PostgresHolidayRepository {
+    // Replaces the holidays of the given year in a single transaction.
+    // The upsert on `holiday_years` comes first, so its row lock serialises concurrent saves of the year.
+    async fn save_holidays(&self, holidays: Vec<Holiday>, year: i32) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        touch_year(&mut tx, year, now_in(self.timezone)).await?;
+        replace_year(&mut tx, year, &holidays).await?;
+
+        tx.commit().await?;
+        println!(
+            "Saved {} holidays for year {} to PostgreSQL",
+            holidays.len(),
+            year
+        );
+
+        Ok(())
+    }
+
+    // Retrieves the holidays of a year ordered by date.
+    // Returns a NotFound error if the year has never been saved.
+    async fn get_holidays_by_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+        if !self.holidays_exist_for_year(year).await? {
+            return Err(AppError::NotFound(format!(
+                "Holiday data not found for year {}",
+                year
+            )));
+        }
+
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE year = $1 ORDER BY date, id",
+        )
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(holiday_from_row).collect()
+    }
+
+    // Checks whether the year has been saved.
+    async fn holidays_exist_for_year(&self, year: i32) -> AppResult<bool> {
+        let found: Option<i32> =
+            sqlx::query_scalar("SELECT year FROM holiday_years WHERE year = $1")
+                .bind(year)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(found.is_some())
+    }
+
+    // Lists the saved years in ascending order.
+    async fn list_available_years(&self) -> AppResult<Vec<i32>> {
+        let years: Vec<i32> = sqlx::query_scalar("SELECT year FROM holiday_years ORDER BY year")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(years)
+    }
+
+    // Retrieves the holidays between two dates (both inclusive) using the date index.
+    async fn get_holidays_in_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<Holiday>> {
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE date BETWEEN $1 AND $2 ORDER BY date, id",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(holiday_from_row).collect()
+    }
+
+    // Retrieves the holidays on a specific date using the date index.
+    async fn find_by_date(&self, date: NaiveDate) -> AppResult<Vec<Holiday>> {
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE date = $1 ORDER BY id",
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(holiday_from_row).collect()
+    }
+
+    // Deletes the year; its holidays are removed by the cascading foreign key.
+    async fn delete_year(&self, year: i32) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM holiday_years WHERE year = $1")
+            .bind(year)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Holiday data not found for year {}",
+                year
+            )));
+        }
+
+        println!("Deleted holidays for year {} from PostgreSQL", year);
+        Ok(())
+    }
+
+    // Reads, changes and replaces the holidays of a year in one transaction.
+    // touch_year comes first, so the year stays locked from the read until the commit.
+    async fn update_year(&self, year: i32, update: HolidayUpdate) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        touch_year(&mut tx, year, now_in(self.timezone)).await?;
+
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE year = $1 ORDER BY date, id",
+        )
+        .bind(year)
+        .fetch_all(&mut *tx)
+        .await?;
+        let existing = rows
+            .iter()
+            .map(holiday_from_row)
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let holidays = update(existing);
+        replace_year(&mut tx, year, &holidays).await?;
+        tx.commit().await?;
+        println!(
+            "Updated year {} in PostgreSQL ({} holidays)",
+            year,
+            holidays.len()
+        );
+
+        Ok(())
+    }
+}
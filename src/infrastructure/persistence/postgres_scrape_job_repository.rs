@@ -0,0 +1,146 @@
+use crate::domain::entities::holiday_merge::MergeStrategy;
+use crate::domain::entities::scrape_job::{JobStatus, ScrapeJob};
+use crate::domain::repositories::scrape_job_repository::ScrapeJobRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::Row;
+
+// How many finished (succeeded or failed) jobs are kept; older ones are deleted when a job finishes.
+const MAX_FINISHED_JOBS: i64 = 100;
+
+// The columns of a `scrape_jobs` row, in the order job_from_row reads them.
+const JOB_COLUMNS: &str = "id, year, strategy, status, owner, created_at, started_at, finished_at, holidays_scraped, error, conflicts";
+
+// PostgresScrapeJobRepository stores scrape jobs in the `scrape_jobs` table of the PostgreSQL database, so every replica sees every job.
+// A job is claimed with a conditional update of its status, so two workers never run the same job.
+// The conflicts of a finished job are stored as JSON. It shares the pool of PostgresHolidayRepository, which creates the table
+// in its migrations.
+pub struct PostgresScrapeJobRepository {
+    pool: PgPool,
+}
+
+impl PostgresScrapeJobRepository {
+    // Constructor to initialize PostgresScrapeJobRepository with a migrated connection pool.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Maps a `scrape_jobs` row to a domain scrape job.
+fn job_from_row(row: &PgRow) -> AppResult<ScrapeJob> {
+    let status: String = row.try_get("status")?;
+    let strategy: Option<String> = row.try_get("strategy")?;
+    let conflicts: String = row.try_get("conflicts")?;
+    let holidays_scraped: Option<i64> = row.try_get("holidays_scraped")?;
+    Ok(ScrapeJob {
+        id: row.try_get("id")?,
+        year: row.try_get("year")?,
+        strategy: strategy
+            .map(|strategy| {
+                MergeStrategy::parse(&strategy).ok_or_else(|| {
+                    AppError::InternalServer(format!("Unknown merge strategy '{}'", strategy))
+                })
+            })
+            .transpose()?,
+        status: JobStatus::parse(&status).ok_or_else(|| {
+            AppError::InternalServer(format!("Unknown scrape job status '{}'", status))
+        })?,
+        owner: row.try_get("owner")?,
+        created_at: row.try_get("created_at")?,
+        started_at: row.try_get("started_at")?,
+        finished_at: row.try_get("finished_at")?,
+        holidays_scraped: holidays_scraped.map(|count| count as usize),
+        error: row.try_get("error")?,
+        conflicts: serde_json::from_str(&conflicts)?,
+    })
+}
+
+#[async_trait]
+impl ScrapeJobRepository for PostgresScrapeJobRepository {
+    // Inserts the job or replaces the stored job with the same id, deleting old finished jobs.
+    async fn save_job(&self, job: ScrapeJob) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!(
+            "INSERT INTO scrape_jobs ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (id) DO UPDATE SET year = EXCLUDED.year, strategy = EXCLUDED.strategy,
+                 status = EXCLUDED.status, owner = EXCLUDED.owner, created_at = EXCLUDED.created_at,
+                 started_at = EXCLUDED.started_at, finished_at = EXCLUDED.finished_at,
+                 holidays_scraped = EXCLUDED.holidays_scraped, error = EXCLUDED.error,
+                 conflicts = EXCLUDED.conflicts",
+            JOB_COLUMNS
+        ))
+        .bind(&job.id)
+        .bind(job.year)
+        .bind(job.strategy.map(|strategy| strategy.as_str()))
+        .bind(job.status.as_str())
+        .bind(&job.owner)
+        .bind(job.created_at)
+        .bind(job.started_at)
+        .bind(job.finished_at)
+        .bind(job.holidays_scraped.map(|count| count as i64))
+        .bind(&job.error)
+        .bind(serde_json::to_string(&job.conflicts)?)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "DELETE FROM scrape_jobs WHERE status IN ('succeeded', 'failed') AND id NOT IN (
+                SELECT id FROM scrape_jobs WHERE status IN ('succeeded', 'failed')
+                ORDER BY finished_at DESC LIMIT $1
+            )",
+        )
+        .bind(MAX_FINISHED_JOBS)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // Looks up a job by id.
+    async fn get_job(&self, id: &str) -> AppResult<Option<ScrapeJob>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM scrape_jobs WHERE id = $1",
+            JOB_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(job_from_row).transpose()
+    }
+
+    // Marks the oldest queued job as running by the given instance. The job row is locked while it is claimed,
+    // and jobs that another replica is claiming at the same time are skipped rather than waited for.
+    async fn claim_next_job(
+        &self,
+        owner: &str,
+        now: NaiveDateTime,
+    ) -> AppResult<Option<ScrapeJob>> {
+        let row = sqlx::query(&format!(
+            "UPDATE scrape_jobs SET status = 'running', owner = $1, started_at = $2
+             WHERE id = (
+                 SELECT id FROM scrape_jobs WHERE status = 'queued'
+                 ORDER BY created_at, id LIMIT 1 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            JOB_COLUMNS
+        ))
+        .bind(owner)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(job_from_row).transpose()
+    }
+
+    // Requeues the running jobs of the given instance, and those without an owner.
+    async fn requeue_jobs_of(&self, owner: &str) -> AppResult<usize> {
+        let result = sqlx::query(
+            "UPDATE scrape_jobs SET status = 'queued', owner = NULL, started_at = NULL
+             WHERE status = 'running' AND (owner = $1 OR owner IS NULL)",
+        )
+        .bind(owner)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+}
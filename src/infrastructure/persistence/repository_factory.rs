@@ -0,0 +1,192 @@
+use crate::config::Config;
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
+use crate::domain::repositories::holiday_repository::HolidayRepository;
+use crate::domain::repositories::job_run_repository::JobRunRepository;
+use crate::domain::repositories::scrape_job_repository::ScrapeJobRepository;
+use crate::domain::services::leader_election::LeaderElection;
+use crate::infrastructure::persistence::cached_api_key_repository::CachedApiKeyRepository;
+use crate::infrastructure::persistence::cached_repository::CachedHolidayRepository;
+use crate::infrastructure::persistence::file_api_key_repository::FileApiKeyRepository;
+use crate::infrastructure::persistence::file_job_repository::FileScrapeJobRepository;
+use crate::infrastructure::persistence::file_job_run_repository::FileJobRunRepository;
+use crate::infrastructure::persistence::file_leader_lock::FileLeaderLock;
+use crate::infrastructure::persistence::file_override_repository::FileHolidayOverrideRepository;
+use crate::infrastructure::persistence::file_repository::FileHolidayRepository;
+use crate::infrastructure::persistence::file_watcher::watch_data_dir;
+use crate::infrastructure::persistence::json_import::{
+    import_api_key_file, import_json_files, import_override_file,
+};
+use crate::infrastructure::persistence::postgres_api_key_repository::PostgresApiKeyRepository;
+use crate::infrastructure::persistence::postgres_job_run_repository::PostgresJobRunRepository;
+use crate::infrastructure::persistence::postgres_leader_lock::PostgresLeaderLock;
+use crate::infrastructure::persistence::postgres_override_repository::PostgresHolidayOverrideRepository;
+use crate::infrastructure::persistence::postgres_repository::PostgresHolidayRepository;
+use crate::infrastructure::persistence::postgres_scrape_job_repository::PostgresScrapeJobRepository;
+use crate::infrastructure::persistence::sqlite_api_key_repository::SqliteApiKeyRepository;
+use crate::infrastructure::persistence::sqlite_job_run_repository::SqliteJobRunRepository;
+use crate::infrastructure::persistence::sqlite_override_repository::SqliteHolidayOverrideRepository;
+use crate::infrastructure::persistence::sqlite_repository::SqliteHolidayRepository;
+use crate::infrastructure::persistence::sqlite_scrape_job_repository::SqliteScrapeJobRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use chrono_tz::Tz;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Repositories are the stores selected by the STORAGE setting.
+// It contains:
+// - holidays: The scraped holidays.
+// - overrides: The manual corrections layered over the scraped holidays.
+// - api_keys: The API keys created over the API, cached in memory for API_KEY_CACHE_TTL_SECS.
+// - scrape_jobs: The queue of scrape jobs, shared by the replicas using the same storage.
+// - job_runs: The run history of the scheduled jobs, which the scheduler catches up from.
+pub struct Repositories {
+    pub holidays: Arc<dyn HolidayRepository>,
+    pub overrides: Arc<dyn HolidayOverrideRepository>,
+    pub api_keys: Arc<dyn ApiKeyRepository>,
+    pub scrape_jobs: Arc<dyn ScrapeJobRepository>,
+    pub job_runs: Arc<dyn JobRunRepository>,
+}
+
+// Creates the repositories selected by the STORAGE setting.
+// - "file": JSON files in DATA_DIR (the default). Unless CACHE_ENABLED is false, the holiday files are
+//   served from an in-memory index that is reloaded when they change on disk.
+// - "sqlite": A SQLite database at SQLITE_PATH.
+// - "postgres": A PostgreSQL database at DATABASE_URL, shared by all replicas.
+// For the database backends, existing JSON files in DATA_DIR are imported on first use, and timestamps
+// are recorded in the given service time zone.
+pub async fn create_repositories(config: &Config, timezone: Tz) -> AppResult<Repositories> {
+    let cache_keys = |api_keys: Arc<dyn ApiKeyRepository>| -> Arc<dyn ApiKeyRepository> {
+        Arc::new(CachedApiKeyRepository::new(
+            api_keys,
+            Duration::from_secs(config.api_key_cache_ttl_secs),
+        ))
+    };
+
+    match config.storage.as_str() {
+        "file" => {
+            let overrides = Arc::new(FileHolidayOverrideRepository::new(config.data_dir.clone()));
+            let api_keys = cache_keys(Arc::new(FileApiKeyRepository::new(config.data_dir.clone())));
+            let scrape_jobs = Arc::new(FileScrapeJobRepository::new(config.data_dir.clone()));
+            let job_runs = Arc::new(FileJobRunRepository::new(config.data_dir.clone()));
+            let repository = FileHolidayRepository::new(config.data_dir.clone());
+            if !config.cache_enabled {
+                return Ok(Repositories {
+                    holidays: Arc::new(repository),
+                    overrides,
+                    api_keys,
+                    scrape_jobs,
+                    job_runs,
+                });
+            }
+
+            let years = repository.stored_years().await?;
+            let cache = Arc::new(CachedHolidayRepository::new(Arc::new(repository)));
+            cache.load_years(years).await;
+            watch_data_dir(&config.data_dir, cache.clone())?;
+            Ok(Repositories {
+                holidays: cache,
+                overrides,
+                api_keys,
+                scrape_jobs,
+                job_runs,
+            })
+        }
+        "sqlite" => {
+            let repository = SqliteHolidayRepository::new(&config.sqlite_path, timezone).await?;
+            let overrides = SqliteHolidayOverrideRepository::new(repository.pool());
+            let api_keys = SqliteApiKeyRepository::new(repository.pool());
+            let scrape_jobs = SqliteScrapeJobRepository::new(repository.pool());
+            let job_runs = SqliteJobRunRepository::new(repository.pool());
+            import_data_dir(config, &repository, &overrides, &api_keys, "SQLite").await?;
+            Ok(Repositories {
+                holidays: Arc::new(repository),
+                overrides: Arc::new(overrides),
+                api_keys: cache_keys(Arc::new(api_keys)),
+                scrape_jobs: Arc::new(scrape_jobs),
+                job_runs: Arc::new(job_runs),
+            })
+        }
+        "postgres" => {
+            let database_url = config.database_url.as_deref().ok_or_else(|| {
+                AppError::Configuration(
+                    "DATABASE_URL is required when STORAGE=postgres".to_string(),
+                )
+            })?;
+            let repository = PostgresHolidayRepository::new(
+                database_url,
+                config.database_max_connections,
+                timezone,
+            )
+            .await?;
+            let overrides = PostgresHolidayOverrideRepository::new(repository.pool());
+            let api_keys = PostgresApiKeyRepository::new(repository.pool());
+            let scrape_jobs = PostgresScrapeJobRepository::new(repository.pool());
+            let job_runs = PostgresJobRunRepository::new(repository.pool());
+            import_data_dir(config, &repository, &overrides, &api_keys, "PostgreSQL").await?;
+            Ok(Repositories {
+                holidays: Arc::new(repository),
+                overrides: Arc::new(overrides),
+                api_keys: cache_keys(Arc::new(api_keys)),
+                scrape_jobs: Arc::new(scrape_jobs),
+                job_runs: Arc::new(job_runs),
+            })
+        }
+        other => Err(AppError::Configuration(format!(
+            "Unknown STORAGE '{}', expected 'file', 'sqlite' or 'postgres'",
+            other
+        ))),
+    }
+}
+
+// Imports the JSON holiday, override and API key files of DATA_DIR into a database backend.
+async fn import_data_dir(
+    config: &Config,
+    holidays: &dyn HolidayRepository,
+    overrides: &dyn HolidayOverrideRepository,
+    api_keys: &dyn ApiKeyRepository,
+    backend: &str,
+) -> AppResult<()> {
+    let imported = import_json_files(&config.data_dir, holidays).await?;
+    if imported > 0 {
+        println!(
+            "Imported {} year(s) of JSON holiday data into {}",
+            imported, backend
+        );
+    }
+
+    let imported = import_override_file(&config.data_dir, overrides).await?;
+    if imported > 0 {
+        println!(
+            "Imported {} override(s) from overrides.json into {}",
+            imported, backend
+        );
+    }
+
+    let imported = import_api_key_file(&config.data_dir, api_keys).await?;
+    if imported > 0 {
+        println!(
+            "Imported {} API key(s) from state/api_keys.json into {}",
+            imported, backend
+        );
+    }
+    Ok(())
+}
+
+// Creates the leader election for the scheduler, matching the STORAGE setting, so that of several
+// replicas sharing the same storage only one runs the scheduled jobs.
+// - "file" and "sqlite": A lock file in DATA_DIR/state, which must be on storage shared by the replicas.
+// - "postgres": An advisory lock in the database at DATABASE_URL.
+pub fn create_leader_election(config: &Config) -> AppResult<Arc<dyn LeaderElection>> {
+    match config.storage.as_str() {
+        "postgres" => {
+            let database_url = config.database_url.as_deref().ok_or_else(|| {
+                AppError::Configuration(
+                    "DATABASE_URL is required when STORAGE=postgres".to_string(),
+                )
+            })?;
+            Ok(Arc::new(PostgresLeaderLock::new(database_url)))
+        }
+        _ => Ok(Arc::new(FileLeaderLock::new(config.data_dir.clone()))),
+    }
+}
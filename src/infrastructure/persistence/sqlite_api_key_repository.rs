@@ -0,0 +1,94 @@
+use crate::domain::entities::api_key::{ApiKey, ApiKeyScope};
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::shared::errors::app_error::{AppError, AppResult};
+use async_trait::async_trait;
+use sqlx::sqlite::{SqlitePool, SqliteRow};
+use sqlx::Row;
+
+// SqliteApiKeyRepository stores API keys in the `api_keys` table of the SQLite database.
+// Only the hashes of the secrets are stored, and the scopes as a comma-separated list, e.g. "read,scrape".
+// It shares the pool of SqliteHolidayRepository, which creates the table in its migrations.
+pub struct SqliteApiKeyRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteApiKeyRepository {
+    // Constructor to initialize SqliteApiKeyRepository with a migrated connection pool.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+// Maps an `api_keys` row to a domain API key.
+fn api_key_from_row(row: &SqliteRow) -> AppResult<ApiKey> {
+    let scopes: String = row.try_get("scopes")?;
+    Ok(ApiKey {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        key_hash: row.try_get("key_hash")?,
+        scopes: scopes
+            .split(',')
+            .filter(|scope| !scope.is_empty())
+            .map(|scope| {
+                ApiKeyScope::parse(scope).ok_or_else(|| {
+                    AppError::InternalServer(format!("Unknown API key scope '{}'", scope))
+                })
+            })
+            .collect::<AppResult<_>>()?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+#[async_trait]
+impl ApiKeyRepository for SqliteApiKeyRepository {
+    // Inserts the key.
+    async fn save_key(&self, api_key: ApiKey) -> AppResult<()> {
+        let scopes: Vec<&str> = api_key.scopes.iter().map(|scope| scope.as_str()).collect();
+        sqlx::query(
+            "INSERT INTO api_keys (id, name, key_hash, scopes, created_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&api_key.id)
+        .bind(&api_key.name)
+        .bind(&api_key.key_hash)
+        .bind(scopes.join(","))
+        .bind(api_key.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // Lists all keys, ordered by creation time.
+    async fn list_keys(&self) -> AppResult<Vec<ApiKey>> {
+        let rows = sqlx::query(
+            "SELECT id, name, key_hash, scopes, created_at FROM api_keys ORDER BY created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(api_key_from_row).collect()
+    }
+
+    // Finds the key with the given hash.
+    async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>> {
+        let row = sqlx::query(
+            "SELECT id, name, key_hash, scopes, created_at FROM api_keys WHERE key_hash = ?",
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(api_key_from_row).transpose()
+    }
+
+    // Deletes the key with the given id.
+    async fn delete_key(&self, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM api_keys WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("API key {} not found", id)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,368 @@
+use crate::domain::entities::holiday::Holiday;
+use crate::domain::repositories::holiday_repository::{HolidayRepository, HolidayUpdate};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use crate::shared::utils::date_utils::now_in;
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+    SqliteRow,
+};
+use sqlx::Row;
+use std::path::Path;
+use std::str::FromStr;
+
+// Schema migrations, applied in order. Each entry is (version, SQL).
+// Applied versions are recorded in `schema_migrations`, so new migrations are only ever appended.
+const MIGRATIONS: &[(i64, &str)] = &[
+    (
+        1,
+        "CREATE TABLE holiday_years (
+        year INTEGER PRIMARY KEY,
+        saved_at TEXT NOT NULL
+    );
+    CREATE TABLE holidays (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        year INTEGER NOT NULL REFERENCES holiday_years(year) ON DELETE CASCADE,
+        date TEXT NOT NULL,
+        description TEXT NOT NULL,
+        is_joint_leave INTEGER NOT NULL
+    );
+    CREATE INDEX idx_holidays_year ON holidays(year);
+    CREATE INDEX idx_holidays_date ON holidays(date);",
+    ),
+    (
+        2,
+        "ALTER TABLE holidays ADD COLUMN is_manual INTEGER NOT NULL DEFAULT 0;",
+    ),
+    (
+        3,
+        "CREATE TABLE holiday_overrides (
+        id TEXT PRIMARY KEY,
+        year INTEGER NOT NULL,
+        date TEXT NOT NULL,
+        action TEXT NOT NULL,
+        match_description TEXT,
+        description TEXT,
+        is_joint_leave INTEGER,
+        created_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_holiday_overrides_year ON holiday_overrides(year);",
+    ),
+    (
+        4,
+        "INSERT INTO holiday_overrides (id, year, date, action, match_description, description, is_joint_leave, created_at)
+        SELECT 'curated-' || id, year, date, 'add', NULL, description, is_joint_leave, datetime('now')
+        FROM holidays WHERE is_manual = 1;
+    DELETE FROM holidays WHERE is_manual = 1;
+    ALTER TABLE holidays DROP COLUMN is_manual;",
+    ),
+    (
+        5,
+        "CREATE TABLE api_keys (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        key_hash TEXT NOT NULL UNIQUE,
+        scopes TEXT NOT NULL,
+        created_at TEXT NOT NULL
+    );",
+    ),
+    (
+        6,
+        "CREATE TABLE scrape_jobs (
+        id TEXT PRIMARY KEY,
+        year INTEGER NOT NULL,
+        strategy TEXT,
+        status TEXT NOT NULL,
+        owner TEXT,
+        created_at TEXT NOT NULL,
+        started_at TEXT,
+        finished_at TEXT,
+        holidays_scraped INTEGER,
+        error TEXT,
+        conflicts TEXT NOT NULL
+    );
+    CREATE INDEX idx_scrape_jobs_status ON scrape_jobs(status, created_at);",
+    ),    (
+        7,
+        "CREATE TABLE job_runs (
+        id TEXT PRIMARY KEY,
+        job TEXT NOT NULL,
+        scheduled_for TEXT NOT NULL,
+        started_at TEXT NOT NULL,
+        finished_at TEXT NOT NULL,
+        status TEXT NOT NULL,
+        message TEXT NOT NULL
+    );
+    CREATE INDEX idx_job_runs_job ON job_runs(job, started_at);",
+    ),
+];
+
+// SqliteHolidayRepository stores holidays in a SQLite database.
+// A year counts as present once it has been saved, even if it has no holidays,
+// which mirrors the file repository where the year's file exists.
+// Its timestamps (`saved_at`, `applied_at`) are recorded in the service time zone.
+pub struct SqliteHolidayRepository {
+    pool: SqlitePool,
+    timezone: Tz,
+}
+
+impl SqliteHolidayRepository {
+    // Opens (or creates) the database at the given path and applies pending migrations.
+    // Timestamps are recorded in the given service time zone.
+    // Returns a Configuration error if the database cannot be opened at the path (SQLITE_PATH).
+    pub async fn new(database_path: &str, timezone: Tz) -> AppResult<Self> {
+        let unusable_path = |e: &dyn std::fmt::Display| {
+            AppError::Configuration(format!(
+                "Cannot open the SQLite database at SQLITE_PATH '{}': {}",
+                database_path, e
+            ))
+        };
+        if let Some(parent) = Path::new(database_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| unusable_path(&e))?;
+            }
+        }
+
+        let options = SqliteConnectOptions::from_str(&format!("sqlite://{}", database_path))
+            .map_err(|e| unusable_path(&e))?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .map_err(|e| unusable_path(&e))?;
+
+        let repository = Self { pool, timezone };
+        repository.run_migrations().await?;
+        Ok(repository)
+    }
+
+    // Returns the connection pool, so the other repositories of this backend share it.
+    pub fn pool(&self) -> SqlitePool {
+        self.pool.clone()
+    }
+
+    // Applies every migration that has not been recorded in `schema_migrations` yet.
+    // Each migration runs in its own transaction together with its bookkeeping row.
+    async fn run_migrations(&self) -> AppResult<()> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                applied_at TEXT NOT NULL
+            )",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        for (version, sql) in MIGRATIONS {
+            let applied: Option<i64> =
+                sqlx::query_scalar("SELECT version FROM schema_migrations WHERE version = ?")
+                    .bind(version)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if applied.is_some() {
+                continue;
+            }
+
+            let mut tx = self.pool.begin().await?;
+            sqlx::raw_sql(sql).execute(&mut *tx).await?;
+            sqlx::query("INSERT INTO schema_migrations (version, applied_at) VALUES (?, ?)")
+                .bind(version)
+                .bind(now_in(self.timezone))
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+
+            println!("Applied SQLite schema migration {}", version);
+        }
+
+        Ok(())
+    }
+}
+
+// Maps a `holidays` row (date, description, is_joint_leave) to a domain holiday.
+fn holiday_from_row(row: &SqliteRow) -> AppResult<Holiday> {
+    Ok(Holiday::new(
+        row.try_get::<NaiveDate, _>("date")?,
+        row.try_get("description")?,
+        row.try_get("is_joint_leave")?,
+    ))
+}
+
+// Creates the year, or refreshes its `saved_at` to the given time. As the first write of a transaction it locks the year,
+// so concurrent saves and updates of the year are serialised.
+async fn touch_year(
+    conn: &mut SqliteConnection,
+    year: i32,
+    saved_at: NaiveDateTime,
+) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO holiday_years (year, saved_at) VALUES (?, ?)
+         ON CONFLICT(year) DO UPDATE SET saved_at = excluded.saved_at",
+    )
+    .bind(year)
+    .bind(saved_at)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+// Replaces the holidays of a year within the caller's transaction, after touch_year has locked the year.
+async fn replace_year(
+    conn: &mut SqliteConnection,
+    year: i32,
+    holidays: &[Holiday],
+) -> AppResult<()> {
+    sqlx::query("DELETE FROM holidays WHERE year = ?")
+        .bind(year)
+        .execute(&mut *conn)
+        .await?;
+
+    for holiday in holidays {
+        sqlx::query(
+            "INSERT INTO holidays (year, date, description, is_joint_leave) VALUES (?, ?, ?, ?)",
+        )
+        .bind(year)
+        .bind(holiday.date)
+        .bind(&holiday.description)
+        .bind(holiday.is_joint_leave)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl HolidayRepository for SqliteHolidayRepository {
+    // Replaces the holidays of the given year in a single transaction.
+    async fn save_holidays(&self, holidays: Vec<Holiday>, year: i32) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        touch_year(&mut tx, year, now_in(self.timezone)).await?;
+        replace_year(&mut tx, year, &holidays).await?;
+
+        tx.commit().await?;
+        println!(
+            "Saved {} holidays for year {} to SQLite",
+            holidays.len(),
+            year
+        );
+
+        Ok(())
+    }
+
+    // Retrieves the holidays of a year ordered by date.
+    // Returns a NotFound error if the year has never been saved.
+    async fn get_holidays_by_year(&self, year: i32) -> AppResult<Vec<Holiday>> {
+        if !self.holidays_exist_for_year(year).await? {
+            return Err(AppError::NotFound(format!(
+                "Holiday data not found for year {}",
+                year
+            )));
+        }
+
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE year = ? ORDER BY date, id",
+        )
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(holiday_from_row).collect()
+    }
+
+    // Checks whether the year has been saved.
+    async fn holidays_exist_for_year(&self, year: i32) -> AppResult<bool> {
+        let found: Option<i64> =
+            sqlx::query_scalar("SELECT year FROM holiday_years WHERE year = ?")
+                .bind(year)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(found.is_some())
+    }
+
+    // Lists the saved years in ascending order.
+    async fn list_available_years(&self) -> AppResult<Vec<i32>> {
+        let years: Vec<i32> = sqlx::query_scalar("SELECT year FROM holiday_years ORDER BY year")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(years)
+    }
+
+    // Retrieves the holidays between two dates (both inclusive) using the date index.
+    async fn get_holidays_in_range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<Holiday>> {
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE date BETWEEN ? AND ? ORDER BY date, id",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(holiday_from_row).collect()
+    }
+
+    // Retrieves the holidays on a specific date using the date index.
+    async fn find_by_date(&self, date: NaiveDate) -> AppResult<Vec<Holiday>> {
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE date = ? ORDER BY id",
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(holiday_from_row).collect()
+    }
+
+    // Deletes the year; its holidays are removed by the cascading foreign key.
+    async fn delete_year(&self, year: i32) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM holiday_years WHERE year = ?")
+            .bind(year)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Holiday data not found for year {}",
+                year
+            )));
+        }
+
+        println!("Deleted holidays for year {} from SQLite", year);
+        Ok(())
+    }
+
+    // Reads, changes and replaces the holidays of a year in one transaction.
+    // touch_year comes first, so the year stays locked from the read until the commit.
+    async fn update_year(&self, year: i32, update: HolidayUpdate) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        touch_year(&mut tx, year, now_in(self.timezone)).await?;
+
+        let rows = sqlx::query(
+            "SELECT date, description, is_joint_leave FROM holidays WHERE year = ? ORDER BY date, id",
+        )
+        .bind(year)
+        .fetch_all(&mut *tx)
+        .await?;
+        let existing = rows
+            .iter()
+            .map(holiday_from_row)
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let holidays = update(existing);
+        replace_year(&mut tx, year, &holidays).await?;
+        tx.commit().await?;
+        println!(
+            "Updated year {} in SQLite ({} holidays)",
+            year,
+            holidays.len()
+        );
+
+        Ok(())
+    }
+}
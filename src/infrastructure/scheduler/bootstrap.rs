@@ -0,0 +1,36 @@
+use chrono::Datelike;
+use chrono_tz::Tz;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+use crate::application::use_cases::backfill_holidays::BackfillHolidaysUseCase;
+use crate::shared::utils::date_utils::today_in;
+use crate::shared::utils::shutdown::Shutdown;
+
+// Starts the bootstrap of the holiday data in the background: the current and the next year are scraped if they
+// have no data yet, e.g. on a fresh install. The next year is usually only published in Q4, with its joint decree;
+// until then the source has no holidays for it, which is reported as not published yet rather than as a failure.
+// It runs on every start of every replica, whether or not the scheduler is enabled or this instance is its
+// leader; years that already have data are skipped, so replicas sharing the storage scrape them only once.
+// On shutdown the bootstrap is abandoned, and the returned handle completes.
+pub async fn start_bootstrap(
+    backfill_use_case: Arc<BackfillHolidaysUseCase>,
+    timezone: Tz,
+    shutdown: Shutdown,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let from = today_in(timezone).year();
+        let to = from + 1;
+
+        tokio::select! {
+            result = backfill_use_case.execute(from, to) => match result {
+                Ok(report) => println!(
+                    "🌱 Bootstrapped holidays for {}-{}: {} scraped, {} already present, {} not published yet, {} failed",
+                    from, to, report.scraped, report.skipped, report.unpublished, report.failed
+                ),
+                Err(e) => println!("❌ Failed to bootstrap holidays: {:?}", e),
+            },
+            _ = shutdown.wait() => println!("⏹️  Holiday bootstrap interrupted by shutdown"),
+        }
+    })
+}
@@ -0,0 +1,191 @@
+use chrono::DateTime;
+use chrono_tz::Tz;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::sleep;
+
+use crate::application::use_cases::scheduled_jobs::ScheduledJobsUseCase;
+use crate::domain::entities::scheduled_job::ScheduledJob;
+use crate::domain::services::leader_election::LeadershipChange;
+use crate::shared::utils::shutdown::Shutdown;
+
+// How often the leader election is run: a follower takes over at most this long after the leader is gone.
+const LEADER_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+// Delay before the queue of requested runs is retried after recording a run failed.
+const ERROR_BACKOFF: Duration = Duration::from_secs(5);
+
+// Starts the scheduler: every scheduled job gets its own task that sleeps until the job's next cron
+// occurrence (in the service time zone) and then runs it, so a long-running job never delays the others.
+// Of several replicas, only the elected leader runs the jobs. When an instance becomes the leader, it catches up
+// runs missed while no instance was running. Runs requested over the API are queued and run by the leader too.
+// On shutdown, running jobs are finished before leadership is given up, so no other instance runs them
+// at the same time. The returned handle completes once the scheduler has stopped.
+pub async fn start_scheduler(
+    use_case: Arc<ScheduledJobsUseCase>,
+    shutdown: Shutdown,
+) -> JoinHandle<()> {
+    println!("🕐 Scheduler started with {} job(s)", use_case.jobs().len());
+
+    tokio::spawn(async move {
+        let mut job_tasks = JoinSet::new();
+        for job in use_case.jobs().to_vec() {
+            job_tasks.spawn(run_schedule(use_case.clone(), job, shutdown.clone()));
+        }
+        job_tasks.spawn(run_triggered(use_case.clone(), shutdown.clone()));
+
+        maintain_leadership(use_case.clone(), shutdown).await;
+
+        while job_tasks.join_next().await.is_some() {}
+        if let Err(e) = use_case.resign().await {
+            println!("❌ Failed to give up scheduler leadership: {:?}", e);
+        }
+        println!("🛑 Scheduler stopped");
+    })
+}
+
+// Runs the leader election periodically until shutdown, taking over the scheduled work when this instance
+// becomes the leader.
+async fn maintain_leadership(use_case: Arc<ScheduledJobsUseCase>, shutdown: Shutdown) {
+    while !shutdown.is_triggered() {
+        match use_case.refresh_leadership().await {
+            Ok(LeadershipChange::Acquired) => {
+                println!("👑 This instance is now the scheduler leader");
+                take_over(&use_case, &shutdown).await;
+            }
+            Ok(LeadershipChange::Lost) => {
+                println!("⚠️  This instance is no longer the scheduler leader");
+            }
+            Ok(LeadershipChange::Kept) | Ok(LeadershipChange::Follower) => {}
+            Err(e) => println!("❌ Scheduler leader election failed: {:?}", e),
+        }
+
+        tokio::select! {
+            _ = sleep(LEADER_CHECK_INTERVAL) => {}
+            _ = shutdown.wait() => {}
+        }
+    }
+}
+
+// Does the work a new leader may have been left: catches up the runs missed while no instance was the leader.
+// Missing years are bootstrapped on every start instead (see start_bootstrap), independent of leadership.
+async fn take_over(use_case: &ScheduledJobsUseCase, shutdown: &Shutdown) {
+    for job in use_case.jobs() {
+        if shutdown.is_triggered() {
+            return;
+        }
+        match use_case.catch_up(job, use_case.now()).await {
+            Ok(Some(run)) => println!(
+                "🏁 Missed run of {} {}: {}",
+                job.name(),
+                run.status,
+                run.message
+            ),
+            Ok(None) => {}
+            Err(e) => println!("❌ Failed to catch up job {}: {:?}", job.name(), e),
+        }
+    }
+}
+
+// Runs a single job whenever it is due, until its schedule no longer matches any future time.
+// Runs that are due while this instance is not the leader are left to the leader; leadership is confirmed by
+// renewing the lock right before each run.
+// Stops on shutdown, after finishing the run in progress.
+async fn run_schedule(use_case: Arc<ScheduledJobsUseCase>, job: ScheduledJob, shutdown: Shutdown) {
+    // The next run is looked up after the later of now and the previous run, so an occurrence is never run twice,
+    // even if the wall clock is set back after running it.
+    let mut previous_run: Option<DateTime<Tz>> = None;
+    while !shutdown.is_triggered() {
+        let now = use_case.now();
+        let after = previous_run.map_or(now, |previous| previous.max(now));
+        let Some(next_run) = job.next_run_after(&after) else {
+            println!(
+                "⚠️  Job {} ({}) has no upcoming runs",
+                job.name(),
+                job.schedule
+            );
+            return;
+        };
+        previous_run = Some(next_run);
+
+        println!(
+            "⏰ Next run of {} at: {}",
+            job.name(),
+            next_run.format("%Y-%m-%d %H:%M:%S")
+        );
+        if !wait_until(&use_case, next_run, &shutdown).await {
+            return;
+        }
+
+        // The outcome of the last election round may be stale, so leadership is confirmed right before the run
+        if let Err(e) = use_case.confirm_leadership().await {
+            println!("⏭️  Skipping job {}: {}", job.name(), e);
+            continue;
+        }
+
+        println!("📅 Running scheduled job {}", job.name());
+        match use_case.run_job(&job, next_run.naive_local()).await {
+            Ok(run) => println!(
+                "🏁 Scheduled job {} {}: {}",
+                job.name(),
+                run.status,
+                run.message
+            ),
+            Err(e) => println!("❌ Failed to record the run of job {}: {:?}", job.name(), e),
+        }
+    }
+}
+
+// Runs the requested runs of jobs, in the order they were queued, until shutdown.
+// Runs that have not started by then are dropped, since a request is only valid for the leader it was sent to.
+async fn run_triggered(use_case: Arc<ScheduledJobsUseCase>, shutdown: Shutdown) {
+    while !shutdown.is_triggered() {
+        match use_case.run_next_triggered().await {
+            // Keep draining the queue while there is work
+            Ok(true) => continue,
+            // Queue is empty, sleep until a run is requested or the server shuts down
+            Ok(false) => {
+                tokio::select! {
+                    _ = use_case.wait_for_triggered() => {}
+                    _ = shutdown.wait() => {}
+                }
+            }
+            Err(e) => {
+                println!("❌ Failed to record a requested job run: {:?}", e);
+                tokio::select! {
+                    _ = sleep(ERROR_BACKOFF) => {}
+                    _ = shutdown.wait() => {}
+                }
+            }
+        }
+    }
+
+    let discarded = use_case.discard_triggered().await;
+    if discarded > 0 {
+        println!(
+            "⏹️  Dropped {} requested job run(s) that had not started",
+            discarded
+        );
+    }
+}
+
+// Sleeps until the wall clock reaches the given time. Returns false if shutdown was triggered first.
+// Sleeping uses the monotonic clock, which drifts from the wall clock, e.g. when the clock is adjusted or the host
+// is suspended, so the wall clock is checked again after waking up and the sleep is repeated if it is early.
+async fn wait_until(
+    use_case: &ScheduledJobsUseCase,
+    time: DateTime<Tz>,
+    shutdown: &Shutdown,
+) -> bool {
+    loop {
+        let remaining = (time - use_case.now()).to_std().unwrap_or_default();
+        if remaining.is_zero() {
+            return true;
+        }
+        tokio::select! {
+            _ = sleep(remaining) => {}
+            _ = shutdown.wait() => return false,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use crate::application::use_cases::scrape_jobs::ScrapeJobsUseCase;
+use crate::shared::utils::shutdown::Shutdown;
+
+// Delay before the worker retries after the job repository failed.
+const ERROR_BACKOFF: Duration = Duration::from_secs(5);
+
+// How often an idle worker checks the queue for jobs submitted on other replicas.
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+// Starts the background worker that runs queued scrape jobs, one at a time.
+// On shutdown the worker finishes the job it is running and stops; queued jobs stay in the queue
+// for the next start. The returned handle completes once the worker has stopped.
+pub async fn start_job_worker(
+    use_case: Arc<ScrapeJobsUseCase>,
+    shutdown: Shutdown,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        println!("🧰 Scrape job worker started");
+
+        // Jobs that were running when the server stopped are queued again
+        match use_case.recover_interrupted_jobs().await {
+            Ok(0) => {}
+            Ok(count) => println!("🔁 Requeued {} interrupted scrape job(s)", count),
+            Err(e) => println!("❌ Failed to recover interrupted scrape jobs: {:?}", e),
+        }
+
+        while !shutdown.is_triggered() {
+            match use_case.run_next_job().await {
+                // Keep draining the queue while there is work
+                Ok(true) => continue,
+                // Queue is empty, sleep until a job is submitted, another replica may have queued one,
+                // or the server shuts down
+                Ok(false) => {
+                    tokio::select! {
+                        _ = use_case.wait_for_jobs() => {}
+                        _ = sleep(QUEUE_POLL_INTERVAL) => {}
+                        _ = shutdown.wait() => {}
+                    }
+                }
+                Err(e) => {
+                    println!("❌ Scrape job worker error: {:?}", e);
+                    tokio::select! {
+                        _ = sleep(ERROR_BACKOFF) => {}
+                        _ = shutdown.wait() => {}
+                    }
+                }
+            }
+        }
+
+        println!("🛑 Scrape job worker stopped");
+    })
+}
@@ -1 +1,3 @@
-pub mod holiday_scheduler;
\ No newline at end of file
+pub mod bootstrap;
+pub mod job_scheduler;
+pub mod job_worker;
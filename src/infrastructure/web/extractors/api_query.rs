@@ -0,0 +1,34 @@
+use crate::application::dto::api_response::ApiResponse;
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, StatusCode},
+    response::Json,
+};
+use serde::de::DeserializeOwned;
+
+// ApiQuery extracts the query string like axum's Query, but rejects invalid parameters
+// (e.g. `?month=abc`) with the usual ApiResponse body instead of axum's plain-text message.
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ApiResponse<String>>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Query::<T>::from_request_parts(parts, state).await {
+            Ok(Query(value)) => Ok(Self(value)),
+            Err(rejection) => {
+                let status = rejection.status();
+                let response = ApiResponse::error(
+                    status.as_u16() as i16,
+                    &rejection.body_text(),
+                    String::new(),
+                );
+                Err((status, Json(response)))
+            }
+        }
+    }
+}
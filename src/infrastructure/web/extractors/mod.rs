@@ -0,0 +1 @@
+pub mod api_query;
\ No newline at end of file
@@ -0,0 +1,219 @@
+use crate::application::dto::api_key_dto::{ApiKeyDto, CreateApiKeyDto, CreatedApiKeyDto};
+use crate::application::dto::api_response::ApiResponse;
+use crate::application::dto::health_dto::ScraperHealthDto;
+use crate::application::dto::holiday_override_dto::{HolidayCorrectionDto, HolidayOverrideDto};
+use crate::application::dto::job_run_dto::{QueuedJobRunDto, SchedulerStatusDto};
+use crate::application::dto::usage_dto::UsageReportDto;
+use crate::application::use_cases::get_health::GetHealthUseCase;
+use crate::application::use_cases::manage_api_keys::ManageApiKeysUseCase;
+use crate::application::use_cases::manage_overrides::ManageOverridesUseCase;
+use crate::application::use_cases::rate_limit::RateLimitUseCase;
+use crate::application::use_cases::scheduled_jobs::ScheduledJobsUseCase;
+use crate::infrastructure::web::extractors::api_query::ApiQuery;
+use crate::shared::errors::app_error::AppError;
+use axum::{extract::Path, response::Json, Extension};
+use serde::Deserialize;
+use std::sync::Arc;
+
+// UsageQuery holds the day of a usage report, e.g. `?date=2025-04-28`; today if omitted.
+#[derive(Deserialize)]
+pub struct UsageQuery {
+    pub date: Option<String>,
+}
+
+// Maps a use case error to the code of the error response.
+fn error_code(error: &AppError) -> i16 {
+    error.status_code().as_u16() as i16
+}
+
+pub async fn list_overrides(
+    Path(year): Path<i32>,
+    Extension(use_case): Extension<Arc<ManageOverridesUseCase>>,
+) -> Result<Json<ApiResponse<Vec<HolidayOverrideDto>>>, Json<ApiResponse<String>>> {
+    match use_case.list_overrides(year).await {
+        Ok(overrides) => {
+            let response = ApiResponse::success(overrides, "Overrides retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn add_holiday(
+    Path(year): Path<i32>,
+    Extension(use_case): Extension<Arc<ManageOverridesUseCase>>,
+    Json(correction): Json<HolidayCorrectionDto>,
+) -> Result<Json<ApiResponse<HolidayOverrideDto>>, Json<ApiResponse<String>>> {
+    match use_case.add_holiday(year, correction).await {
+        Ok(holiday_override) => {
+            let response = ApiResponse::success(holiday_override, "Holiday added");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn edit_holiday(
+    Path(year): Path<i32>,
+    Extension(use_case): Extension<Arc<ManageOverridesUseCase>>,
+    Json(correction): Json<HolidayCorrectionDto>,
+) -> Result<Json<ApiResponse<HolidayOverrideDto>>, Json<ApiResponse<String>>> {
+    match use_case.edit_holiday(year, correction).await {
+        Ok(holiday_override) => {
+            let response = ApiResponse::success(holiday_override, "Holiday edited");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn remove_holiday(
+    Path(year): Path<i32>,
+    Extension(use_case): Extension<Arc<ManageOverridesUseCase>>,
+    Json(correction): Json<HolidayCorrectionDto>,
+) -> Result<Json<ApiResponse<HolidayOverrideDto>>, Json<ApiResponse<String>>> {
+    match use_case.remove_holiday(year, correction).await {
+        Ok(holiday_override) => {
+            let response = ApiResponse::success(holiday_override, "Holiday removed");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn delete_override(
+    Path((year, id)): Path<(i32, String)>,
+    Extension(use_case): Extension<Arc<ManageOverridesUseCase>>,
+) -> Result<Json<ApiResponse<String>>, Json<ApiResponse<String>>> {
+    match use_case.delete_override(year, &id).await {
+        Ok(()) => {
+            let response = ApiResponse::success(id, "Override withdrawn");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn list_api_keys(
+    Extension(use_case): Extension<Arc<ManageApiKeysUseCase>>,
+) -> Result<Json<ApiResponse<Vec<ApiKeyDto>>>, Json<ApiResponse<String>>> {
+    match use_case.list_keys().await {
+        Ok(keys) => {
+            let response = ApiResponse::success(keys, "API keys retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn create_api_key(
+    Extension(use_case): Extension<Arc<ManageApiKeysUseCase>>,
+    Json(request): Json<CreateApiKeyDto>,
+) -> Result<Json<ApiResponse<CreatedApiKeyDto>>, Json<ApiResponse<String>>> {
+    match use_case.create_key(request).await {
+        Ok(key) => {
+            let response = ApiResponse::success(key, "API key created, store the secret now");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn revoke_api_key(
+    Path(id): Path<String>,
+    Extension(use_case): Extension<Arc<ManageApiKeysUseCase>>,
+) -> Result<Json<ApiResponse<String>>, Json<ApiResponse<String>>> {
+    match use_case.revoke_key(&id).await {
+        Ok(()) => {
+            let response = ApiResponse::success(id, "API key revoked");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_usage(
+    ApiQuery(query): ApiQuery<UsageQuery>,
+    Extension(use_case): Extension<Arc<RateLimitUseCase>>,
+) -> Result<Json<ApiResponse<UsageReportDto>>, Json<ApiResponse<String>>> {
+    match use_case.usage(query.date.as_deref()) {
+        Ok(report) => {
+            let response = ApiResponse::success(report, "Usage retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_scheduler_status(
+    Extension(use_case): Extension<Arc<ScheduledJobsUseCase>>,
+) -> Result<Json<ApiResponse<SchedulerStatusDto>>, Json<ApiResponse<String>>> {
+    match use_case.status().await {
+        Ok(status) => {
+            let response = ApiResponse::success(status, "Scheduler status retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn run_scheduled_job(
+    Path(job): Path<String>,
+    Extension(use_case): Extension<Arc<ScheduledJobsUseCase>>,
+) -> Result<Json<ApiResponse<QueuedJobRunDto>>, Json<ApiResponse<String>>> {
+    match use_case.trigger_job(&job).await {
+        Ok(run) => {
+            let response = ApiResponse::success(run, "Scheduled job run queued");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn accept_layout(
+    Extension(use_case): Extension<Arc<GetHealthUseCase>>,
+) -> Result<Json<ApiResponse<ScraperHealthDto>>, Json<ApiResponse<String>>> {
+    match use_case.accept_layout().await {
+        Ok(health) => {
+            let response = ApiResponse::success(health, "Layout accepted as the new baseline");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(error_code(&e), &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::application::dto::api_response::ApiResponse;
+use crate::application::dto::health_dto::ScraperHealthDto;
+use crate::application::use_cases::get_health::GetHealthUseCase;
+use axum::{response::Json, Extension};
+use std::sync::Arc;
+
+pub async fn get_health(
+    Extension(use_case): Extension<Arc<GetHealthUseCase>>,
+) -> Json<ApiResponse<ScraperHealthDto>> {
+    let health = use_case.execute();
+    let message = if health.status == "healthy" {
+        "Service is healthy"
+    } else {
+        "Service is degraded"
+    };
+    Json(ApiResponse::success(health, message))
+}
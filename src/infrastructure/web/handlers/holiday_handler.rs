@@ -1,44 +1,200 @@
 use crate::application::dto::api_response::ApiResponse;
-use crate::application::dto::holiday_dto::HolidayDto;
+use crate::application::dto::holiday_dto::{
+    GroupedHolidaysResultDto, HolidayDto, TodayHolidaysDto, UpcomingHolidaysDto,
+};
+use crate::application::dto::holiday_filter_dto::HolidayFilterDto;
+use crate::application::dto::scrape_job_dto::ScrapeJobDto;
+use crate::application::dto::scrape_report_dto::ScrapeReportDto;
 use crate::application::use_cases::{
-    get_holidays::GetHolidaysUseCase, scrape_holidays::ScrapeHolidaysUseCase,
+    backfill_holidays::BackfillHolidaysUseCase, get_holidays::GetHolidaysUseCase,
+    scrape_jobs::ScrapeJobsUseCase,
+};
+use crate::domain::entities::api_key::ApiKeyScope;
+use crate::infrastructure::web::extractors::api_query::ApiQuery;
+use crate::infrastructure::web::middleware::api_key_auth::GrantedScopes;
+use crate::shared::errors::app_error::AppError;
+use axum::{
+    extract::Path,
+    http::{header::LINK, HeaderMap, HeaderValue},
+    response::Json,
+    Extension,
 };
-use axum::{extract::Path, response::Json, Extension};
-use std::collections::HashMap;
+use serde::Deserialize;
+use serde_json::Value;
 use std::sync::Arc;
+
+// ScrapeRangeQuery holds the query parameters of a batch scrape, e.g. `?from=2000&to=2026`.
+#[derive(Deserialize)]
+pub struct ScrapeRangeQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+// ScrapeQuery holds the optional merge strategy of a scrape, e.g. `?strategy=add-only`.
+#[derive(Deserialize)]
+pub struct ScrapeQuery {
+    pub strategy: Option<String>,
+}
+
+// GroupedQuery holds the grouping of a grouped listing, e.g. `?by=month`.
+#[derive(Deserialize)]
+pub struct GroupedQuery {
+    pub by: Option<String>,
+}
+
+// HolidayRangeQuery holds the dates of a date-range lookup, e.g. `?from=2024-12-01&to=2025-01-31`.
+// Filters in the same query string are read separately into a HolidayFilterDto.
+#[derive(Deserialize)]
+pub struct HolidayRangeQuery {
+    pub from: String,
+    pub to: String,
+}
+// TimezoneQuery holds the time zone that "today" is taken in, e.g. `?tz=WITA` or `?tz=Asia/Jayapura`.
+#[derive(Deserialize)]
+pub struct TimezoneQuery {
+    pub tz: Option<String>,
+}
+
+// UpcomingQuery holds the time zone and the number of holidays of an upcoming lookup, e.g. `?tz=WIT&limit=3`.
+// Filters in the same query string are read separately into a HolidayFilterDto.
+#[derive(Deserialize)]
+pub struct UpcomingQuery {
+    pub tz: Option<String>,
+    pub limit: Option<usize>,
+}
 pub async fn root() -> &'static str {
     "
 🎉 Welcome to the Holiday API! 
 
-Available endpoints:
+Available endpoints (send an API key as `Authorization: Bearer <key>` or `X-API-Key: <key>`;
+reads are public by default, scraping requires the scrape scope; clients over their rate limit or daily quota get 429):
+
+- POST /scrape?from={year}&to={year}
+    - Scrape every year in the range, skipping years that already have data.
+    - Example: POST /scrape?from=2000&to=2026
+    - Returns the outcome per year (scraped, skipped or failed).
+
+- POST /jobs/scrape
+    - Queue a background scrape job, e.g. with body {\"year\": 2025}. Returns the job id immediately.
+    - An optional merge strategy can be given, e.g. {\"year\": 2025, \"strategy\": \"add-only\"}:
+      replace (requires the admin scope), merge-keep-manual or add-only. Corrections made under
+      /admin/libur/{year} are always kept, and conflicts with them are reported (replace flags them for review).
 
-- GET /scrape/{year} 
-    - Scrape holidays for the specific year from an external source.
-    - Example: GET /scrape/2023
+- GET /scrape/{year}
+    - Deprecated alias of POST /jobs/scrape that queues a scrape job for the year.
+
+- GET /jobs/{id}
+    - Get the status of a scrape job (queued, running, succeeded or failed) and its report.
 
 - GET /libur/{year}
     - Get holidays for a specific year as a list of holidays.
     - Example: GET /libur/2023
     - Returns a list of holidays for the given year.
+    - Optional filters: month, joint_leave, category, q (search), weekday, exclude_weekends and fields.
+    - Example: GET /libur/2023?month=12&exclude_weekends=true&fields=date,description
+
+- GET /libur?from={date}&to={date}
+    - Get the holidays between two dates (YYYY-MM-DD), spanning years if needed.
+    - Accepts the same filters as GET /libur/{year}, e.g. joint_leave=true|false or category=islamic.
+    - Example: GET /libur?from=2024-12-01&to=2025-01-31&joint_leave=false
+
+- GET /libur/years
+    - List the years that have holiday data.
+    - Example: GET /libur/years
+
+- GET /libur/date/{date}
+    - Get the holidays on a specific date, in YYYY-MM-DD format.
+    - Example: GET /libur/date/2024-12-25
+
+- GET /libur/today?tz={timezone}
+    - Tell whether today is a holiday. Today is taken in the service time zone (Asia/Jakarta by default),
+      or in the given one: an IANA name or WIB, WITA or WIT.
+    - Example: GET /libur/today?tz=WITA
+
+- GET /libur/upcoming?tz={timezone}&limit={n}
+    - Get the next holidays from today (up to a year ahead), 5 by default and at most 50.
+    - Accepts the same filters as GET /libur/{year}.
+    - Example: GET /libur/upcoming?tz=WIT&limit=3&joint_leave=false
+
+- GET /libur/{year}/grouped?by={grouping}
+    - Get holidays for a specific year, grouped by type (joint leave vs. non-joint leave, the default),
+      month, weekday, quarter or category.
+    - Example: GET /libur/2023/grouped?by=month
+    - Returns the groups in order, each with its holidays and count.
+
+- GET|POST|PUT|DELETE /admin/libur/{year}
+    - List, add, edit or remove holiday corrections for a year (requires the admin scope).
+    - Corrections are kept as overrides and survive later scrapes.
+
+- GET|POST /admin/keys, DELETE /admin/keys/{id}
+    - List, create or revoke API keys (requires the admin scope).
+    - Example: POST /admin/keys with body {\"name\": \"ci\", \"scopes\": [\"read\", \"scrape\"]}
 
-- GET /libur-grouped/{year}
-    - Get holidays for a specific year, grouped by type (e.g., joint leave vs. non-joint leave).
-    - Example: GET /libur-grouped/2023
-    - Returns holidays grouped by their type.
+- GET /admin/scheduler, POST /admin/scheduler/{job}/run
+    - List the scheduled jobs with their next and last run, or run a job right away (requires the admin scope).
+    - Example: POST /admin/scheduler/validate-data/run
+
+- GET /admin/usage?date={date}
+    - Get the daily request counts per client, including rate-limited requests (requires the admin scope).
+
+- POST /admin/layout/accept
+    - Accept a changed layout of the external site as the new baseline, clearing the layout alert
+      (requires the admin scope).
+
+- GET /health
+    - Get the health of the scraper, including layout-change alerts for the external source.
+    - Example: GET /health
     "
 }
 
+// Deprecated alias of POST /jobs/scrape, kept for existing clients. A GET must not change data, so the scrape is
+// queued as a background job instead of running inside the request; the response says so in its headers.
 pub async fn scrape_holidays(
     Path(year): Path<i32>,
-    Extension(use_case): Extension<Arc<ScrapeHolidaysUseCase>>,
-) -> Result<Json<ApiResponse<Vec<HolidayDto>>>, Json<ApiResponse<String>>> {
-    match use_case.execute(year).await {
-        Ok(holidays) => {
-            let response = ApiResponse::success(holidays, "Holidays scraped successfully");
+    ApiQuery(query): ApiQuery<ScrapeQuery>,
+    Extension(use_case): Extension<Arc<ScrapeJobsUseCase>>,
+    Extension(granted): Extension<GrantedScopes>,
+) -> Result<(HeaderMap, Json<ApiResponse<ScrapeJobDto>>), Json<ApiResponse<String>>> {
+    let allow_replace = granted.grants(ApiKeyScope::Admin);
+    match use_case
+        .submit(year, query.strategy.as_deref(), allow_replace)
+        .await
+    {
+        Ok(job) => {
+            let mut headers = HeaderMap::new();
+            headers.insert("deprecation", HeaderValue::from_static("true"));
+            if let Ok(link) = HeaderValue::from_str(&format!(
+                "</jobs/{}>; rel=\"status\", </jobs/scrape>; rel=\"successor-version\"",
+                job.id
+            )) {
+                headers.insert(LINK, link);
+            }
+            let response = ApiResponse::success(
+                job,
+                "Scrape job queued; GET /scrape/{year} is deprecated, use POST /jobs/scrape",
+            );
+            Ok((headers, Json(response)))
+        }
+        Err(e) => {
+            let code = e.status_code().as_u16() as i16;
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn scrape_holidays_range(
+    ApiQuery(range): ApiQuery<ScrapeRangeQuery>,
+    Extension(use_case): Extension<Arc<BackfillHolidaysUseCase>>,
+) -> Result<Json<ApiResponse<ScrapeReportDto>>, Json<ApiResponse<String>>> {
+    match use_case.execute(range.from, range.to).await {
+        Ok(report) => {
+            let response = ApiResponse::success(report, "Batch scrape completed");
             Ok(Json(response))
         }
         Err(e) => {
-            let response = ApiResponse::error(500, &e.to_string(), String::new());
+            let code = e.status_code().as_u16() as i16;
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
             Err(Json(response))
         }
     }
@@ -46,15 +202,20 @@ pub async fn scrape_holidays(
 
 pub async fn get_holidays(
     Path(year): Path<i32>,
+    ApiQuery(filter): ApiQuery<HolidayFilterDto>,
     Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
-) -> Result<Json<ApiResponse<Vec<HolidayDto>>>, Json<ApiResponse<String>>> {
-    match use_case.execute(year).await {
+) -> Result<Json<ApiResponse<Vec<Value>>>, Json<ApiResponse<String>>> {
+    match use_case.execute(year, &filter).await {
         Ok(holidays) => {
             let response = ApiResponse::success(holidays, "Holidays retrieved successfully");
             Ok(Json(response))
         }
         Err(e) => {
-            let response = ApiResponse::error(404, &e.to_string(), String::new());
+            let code = match e {
+                AppError::BadRequest(_) => 400,
+                _ => 404,
+            };
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
             Err(Json(response))
         }
     }
@@ -62,18 +223,129 @@ pub async fn get_holidays(
 
 pub async fn get_holidays_grouped(
     Path(year): Path<i32>,
+    ApiQuery(grouped): ApiQuery<GroupedQuery>,
+    ApiQuery(filter): ApiQuery<HolidayFilterDto>,
     Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
-) -> Result<Json<ApiResponse<HashMap<String, Vec<HolidayDto>>>>, Json<ApiResponse<String>>> {
-    match use_case.execute_grouped(year).await {
+) -> Result<Json<ApiResponse<GroupedHolidaysResultDto>>, Json<ApiResponse<String>>> {
+    match use_case
+        .execute_grouped(year, grouped.by.as_deref(), &filter)
+        .await
+    {
         Ok(grouped_holidays) => {
-            let response = ApiResponse::success(
-                grouped_holidays.to_hashmap(),
-                "Holidays retrieved successfully",
-            );
+            let response =
+                ApiResponse::success(grouped_holidays, "Holidays retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let code = match e {
+                AppError::BadRequest(_) => 400,
+                _ => 404,
+            };
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_available_years(
+    Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
+) -> Result<Json<ApiResponse<Vec<i32>>>, Json<ApiResponse<String>>> {
+    match use_case.list_available_years().await {
+        Ok(years) => {
+            let response = ApiResponse::success(years, "Available years retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let response = ApiResponse::error(500, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_holidays_by_date(
+    Path(date): Path<String>,
+    Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
+) -> Result<Json<ApiResponse<Vec<HolidayDto>>>, Json<ApiResponse<String>>> {
+    match use_case.find_by_date(&date).await {
+        Ok(holidays) => {
+            let response = ApiResponse::success(holidays, "Holidays retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let code = match e {
+                AppError::BadRequest(_) => 400,
+                _ => 404,
+            };
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_holidays_in_range(
+    ApiQuery(range): ApiQuery<HolidayRangeQuery>,
+    ApiQuery(filter): ApiQuery<HolidayFilterDto>,
+    Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
+) -> Result<Json<ApiResponse<Vec<Value>>>, Json<ApiResponse<String>>> {
+    match use_case
+        .execute_range(&range.from, &range.to, &filter)
+        .await
+    {
+        Ok(holidays) => {
+            let response = ApiResponse::success(holidays, "Holidays retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let code = match e {
+                AppError::BadRequest(_) => 400,
+                _ => 500,
+            };
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_holidays_today(
+    ApiQuery(query): ApiQuery<TimezoneQuery>,
+    Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
+) -> Result<Json<ApiResponse<TodayHolidaysDto>>, Json<ApiResponse<String>>> {
+    match use_case.execute_today(query.tz.as_deref()).await {
+        Ok(today) => {
+            let response = ApiResponse::success(today, "Holidays retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let code = match e {
+                AppError::BadRequest(_) => 400,
+                AppError::NotFound(_) => 404,
+                _ => 500,
+            };
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_upcoming_holidays(
+    ApiQuery(query): ApiQuery<UpcomingQuery>,
+    ApiQuery(filter): ApiQuery<HolidayFilterDto>,
+    Extension(use_case): Extension<Arc<GetHolidaysUseCase>>,
+) -> Result<Json<ApiResponse<UpcomingHolidaysDto>>, Json<ApiResponse<String>>> {
+    match use_case
+        .execute_upcoming(query.tz.as_deref(), query.limit, &filter)
+        .await
+    {
+        Ok(upcoming) => {
+            let response = ApiResponse::success(upcoming, "Holidays retrieved successfully");
             Ok(Json(response))
         }
         Err(e) => {
-            let response = ApiResponse::error(404, &e.to_string(), String::new());
+            let code = match e {
+                AppError::BadRequest(_) => 400,
+                _ => 500,
+            };
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
             Err(Json(response))
         }
     }
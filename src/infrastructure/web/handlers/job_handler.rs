@@ -0,0 +1,49 @@
+use crate::application::dto::api_response::ApiResponse;
+use crate::application::dto::scrape_job_dto::{ScrapeJobDto, SubmitScrapeJobDto};
+use crate::application::use_cases::scrape_jobs::ScrapeJobsUseCase;
+use crate::domain::entities::api_key::ApiKeyScope;
+use crate::infrastructure::web::middleware::api_key_auth::GrantedScopes;
+use axum::{extract::Path, response::Json, Extension};
+use std::sync::Arc;
+
+pub async fn submit_scrape_job(
+    Extension(use_case): Extension<Arc<ScrapeJobsUseCase>>,
+    Extension(granted): Extension<GrantedScopes>,
+    Json(request): Json<SubmitScrapeJobDto>,
+) -> Result<Json<ApiResponse<ScrapeJobDto>>, Json<ApiResponse<String>>> {
+    match use_case
+        .submit(
+            request.year,
+            request.strategy.as_deref(),
+            granted.grants(ApiKeyScope::Admin),
+        )
+        .await
+    {
+        Ok(job) => {
+            let response = ApiResponse::success(job, "Scrape job queued");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let code = e.status_code().as_u16() as i16;
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
+
+pub async fn get_job(
+    Path(id): Path<String>,
+    Extension(use_case): Extension<Arc<ScrapeJobsUseCase>>,
+) -> Result<Json<ApiResponse<ScrapeJobDto>>, Json<ApiResponse<String>>> {
+    match use_case.get_job(&id).await {
+        Ok(job) => {
+            let response = ApiResponse::success(job, "Scrape job retrieved successfully");
+            Ok(Json(response))
+        }
+        Err(e) => {
+            let code = e.status_code().as_u16() as i16;
+            let response = ApiResponse::error(code, &e.to_string(), String::new());
+            Err(Json(response))
+        }
+    }
+}
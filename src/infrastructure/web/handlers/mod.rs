@@ -1 +1,4 @@
-pub mod holiday_handler;
\ No newline at end of file
+pub mod admin_handler;
+pub mod health_handler;
+pub mod holiday_handler;
+pub mod job_handler;
@@ -0,0 +1,181 @@
+use crate::application::dto::api_response::ApiResponse;
+use crate::application::use_cases::manage_api_keys::{Access, ManageApiKeysUseCase};
+use crate::application::use_cases::rate_limit::{RateDecision, RateLimitUseCase};
+use crate::domain::entities::api_key::ApiKeyScope;
+use crate::infrastructure::web::middleware::client_ip::TrustedProxies;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+
+// Header carrying the API key, as an alternative to `Authorization: Bearer <key>`.
+const API_KEY_HEADER: &str = "x-api-key";
+
+// AccessControl bundles what the middleware needs to let a request through:
+// the API key checker, the per-client rate limiter and the proxies trusted to report client addresses.
+#[derive(Clone)]
+pub struct AccessControl {
+    api_keys: Arc<ManageApiKeysUseCase>,
+    rate_limiter: Arc<RateLimitUseCase>,
+    trusted_proxies: TrustedProxies,
+}
+
+impl AccessControl {
+    // Constructor to create the access control from the API key and rate limit use cases
+    // and the trusted proxies.
+    pub fn new(
+        api_keys: Arc<ManageApiKeysUseCase>,
+        rate_limiter: Arc<RateLimitUseCase>,
+        trusted_proxies: TrustedProxies,
+    ) -> Self {
+        Self {
+            api_keys,
+            rate_limiter,
+            trusted_proxies,
+        }
+    }
+
+    // Returns the guard of a route group requiring the given scope.
+    pub fn guard(&self, scope: ApiKeyScope) -> ScopeGuard {
+        ScopeGuard {
+            access: self.clone(),
+            scope,
+        }
+    }
+}
+
+// GrantedScopes are the scopes granted to an admitted request: those of its API key, or the public scopes without
+// a key. The access middleware stores them as a request extension, for handlers that check a further scope.
+#[derive(Clone)]
+pub struct GrantedScopes(pub Vec<ApiKeyScope>);
+
+impl GrantedScopes {
+    // Whether the request may use the required scope.
+    pub fn grants(&self, required: ApiKeyScope) -> bool {
+        self.0.iter().any(|scope| scope.grants(required))
+    }
+}
+
+// ScopeGuard is the state of the access middleware: the access control and the scope a route group requires.
+#[derive(Clone)]
+pub struct ScopeGuard {
+    access: AccessControl,
+    scope: ApiKeyScope,
+}
+
+// Middleware guarding a route group with API keys and rate limits.
+// The client's IP address (see TrustedProxies) is rate limited first, before its API key is looked at,
+// and rejected with 429 and `Retry-After` when its bucket is empty.
+// The key is read from `Authorization: Bearer <key>` or `X-API-Key: <key>`.
+// Requests without a key pass only if the required scope is public.
+// Admitted requests are then rate limited per client (the API key, or the IP address without a key)
+// and rejected with 429 and `Retry-After` when the client's bucket for the scope is empty or its
+// daily quota is used up. The scopes granted to an admitted request are stored as GrantedScopes.
+pub async fn require_scope(
+    State(guard): State<ScopeGuard>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let rate_limiter = &guard.access.rate_limiter;
+    let ip = guard
+        .access
+        .trusted_proxies
+        .client_ip(&request)
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    if let Some(response) = too_many_requests(
+        rate_limiter.check_ip(&ip, guard.scope),
+        &format!("{} requests per minute", rate_limiter.ip_limit()),
+    ) {
+        return response;
+    }
+
+    let headers = request.headers();
+    let secret = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| {
+            headers
+                .get(API_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+        })
+        .map(str::trim)
+        .filter(|secret| !secret.is_empty());
+
+    let (key_id, scopes) = match guard.access.api_keys.authorize(secret, guard.scope).await {
+        Ok(Access::Granted { key_id, scopes }) => (key_id, scopes),
+        Ok(Access::MissingKey) => {
+            return reject(
+                StatusCode::UNAUTHORIZED,
+                &format!(
+                    "An API key with the {} scope is required",
+                    guard.scope.as_str()
+                ),
+            )
+        }
+        Ok(Access::InvalidKey) => return reject(StatusCode::UNAUTHORIZED, "Invalid API key"),
+        Ok(Access::InsufficientScope) => {
+            return reject(
+                StatusCode::FORBIDDEN,
+                &format!(
+                    "The API key does not grant the {} scope",
+                    guard.scope.as_str()
+                ),
+            )
+        }
+        Err(e) => return reject(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    };
+
+    request.extensions_mut().insert(GrantedScopes(scopes));
+
+    let client = match key_id {
+        Some(id) => format!("key:{}", id),
+        None => format!("ip:{}", ip),
+    };
+    match too_many_requests(
+        rate_limiter.check(&client, guard.scope),
+        &format!(
+            "{} {} requests per minute",
+            rate_limiter.limit(guard.scope),
+            guard.scope.as_str()
+        ),
+    ) {
+        Some(response) => response,
+        None => next.run(request).await,
+    }
+}
+
+// Builds the 429 response of a rejected rate limit decision, with `Retry-After`; None if the request is allowed.
+// The limit names the per-minute limit that was exceeded, e.g. "120 read requests per minute".
+fn too_many_requests(decision: RateDecision, limit: &str) -> Option<Response> {
+    let (message, retry_after) = match decision {
+        RateDecision::Allowed => return None,
+        RateDecision::Limited { retry_after } => {
+            (format!("Rate limit of {} exceeded", limit), retry_after)
+        }
+        RateDecision::QuotaExceeded { retry_after } => {
+            ("Daily request quota exceeded".to_string(), retry_after)
+        }
+    };
+
+    // Round up, so clients retrying after the given seconds find a token.
+    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut response = reject(
+        StatusCode::TOO_MANY_REQUESTS,
+        &format!("{}, retry in {} seconds", message, seconds),
+    );
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+    Some(response)
+}
+
+// Builds the rejection response, using the usual ApiResponse body.
+fn reject(status: StatusCode, message: &str) -> Response {
+    let response = ApiResponse::error(status.as_u16() as i16, message, String::new());
+    (status, Json(response)).into_response()
+}
@@ -0,0 +1,154 @@
+use crate::shared::errors::app_error::{AppError, AppResult};
+use axum::extract::{ConnectInfo, Request};
+use std::net::{IpAddr, SocketAddr};
+
+// Header listing the addresses a request was forwarded for, appended to by every proxy on the way.
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+// IpNetwork is an address range in CIDR notation, e.g. "10.0.0.0/8"; a single address is a /32 (or /128).
+#[derive(Clone, Copy, Debug)]
+struct IpNetwork {
+    address: IpAddr,
+    prefix: u32,
+}
+
+impl IpNetwork {
+    // Parses an address or a CIDR range.
+    fn parse(value: &str) -> Option<Self> {
+        let (address, prefix) = match value.split_once('/') {
+            Some((address, prefix)) => (address.parse().ok()?, Some(prefix.parse().ok()?)),
+            None => (value.parse().ok()?, None),
+        };
+        let bits = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix = prefix.unwrap_or(bits);
+        (prefix <= bits).then_some(Self { address, prefix })
+    }
+
+    // Checks whether the address lies in the range.
+    fn contains(&self, address: IpAddr) -> bool {
+        match (self.address, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix).unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix).unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+// TrustedProxies are the reverse proxies whose X-Forwarded-For header is believed.
+// Without trusted proxies the header is ignored and the client is the peer address, so clients cannot
+// pick their own address. Behind trusted proxies the client is the rightmost address of the header that is
+// not a trusted proxy: addresses further left were written by the client itself and cannot be trusted.
+#[derive(Clone, Default, Debug)]
+pub struct TrustedProxies {
+    networks: Vec<IpNetwork>,
+}
+
+impl TrustedProxies {
+    // Parses a comma-separated list of addresses and CIDR ranges, e.g. "10.0.0.0/8,192.168.1.10".
+    pub fn parse(value: &str) -> AppResult<Self> {
+        let networks = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                IpNetwork::parse(entry).ok_or_else(|| {
+                    AppError::Configuration(format!(
+                        "Invalid trusted proxy '{}', expected an IP address or a CIDR range",
+                        entry
+                    ))
+                })
+            })
+            .collect::<AppResult<_>>()?;
+        Ok(Self { networks })
+    }
+
+    // Checks whether the address is a trusted proxy.
+    fn contains(&self, address: IpAddr) -> bool {
+        self.networks
+            .iter()
+            .any(|network| network.contains(address))
+    }
+
+    // Resolves the client address of a request from its peer address and X-Forwarded-For header.
+    // Returns None if the server was not started with connection info.
+    pub fn client_ip(&self, request: &Request) -> Option<IpAddr> {
+        let ConnectInfo(peer) = request.extensions().get::<ConnectInfo<SocketAddr>>()?;
+        let forwarded_for = request
+            .headers()
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        Some(self.resolve(peer.ip(), &forwarded_for))
+    }
+
+    // Walks the forwarding chain from the peer backwards, as long as the hops are trusted proxies.
+    // An unparsable entry ends the walk, and the last trusted hop is taken as the client.
+    fn resolve(&self, peer: IpAddr, forwarded_for: &str) -> IpAddr {
+        let mut client = peer;
+        for hop in forwarded_for.rsplit(',').map(str::trim) {
+            if !self.contains(client) {
+                break;
+            }
+            match hop.parse() {
+                Ok(address) => client = address,
+                Err(_) => break,
+            }
+        }
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn forwarded_for_is_ignored_without_trusted_proxies() {
+        let proxies = TrustedProxies::parse("").unwrap();
+
+        assert_eq!(proxies.resolve(ip("10.0.0.1"), "1.2.3.4"), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn the_rightmost_untrusted_address_is_the_client() {
+        let proxies = TrustedProxies::parse("10.0.0.0/8, 192.168.1.10").unwrap();
+
+        // The leftmost entry was sent by the client and is not believed.
+        assert_eq!(
+            proxies.resolve(ip("10.0.0.1"), "6.6.6.6, 1.2.3.4, 192.168.1.10"),
+            ip("1.2.3.4")
+        );
+        // A request that did not come through a trusted proxy keeps its peer address.
+        assert_eq!(proxies.resolve(ip("5.5.5.5"), "1.2.3.4"), ip("5.5.5.5"));
+        // Garbage in the header stops the walk at the last trusted hop.
+        assert_eq!(
+            proxies.resolve(ip("10.0.0.1"), "1.2.3.4, unknown"),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn invalid_entries_are_a_configuration_error() {
+        assert!(matches!(
+            TrustedProxies::parse("10.0.0.0/33"),
+            Err(AppError::Configuration(_))
+        ));
+        assert!(TrustedProxies::parse("proxy.local").is_err());
+        assert!(TrustedProxies::parse("::1, fd00::/8").is_ok());
+    }
+}
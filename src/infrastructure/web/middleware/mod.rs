@@ -0,0 +1,2 @@
+pub mod api_key_auth;
+pub mod client_ip;
@@ -1,2 +1,4 @@
+pub mod extractors;
 pub mod handlers;
+pub mod middleware;
 pub mod routes;
\ No newline at end of file
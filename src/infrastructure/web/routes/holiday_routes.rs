@@ -1,21 +1,98 @@
 use crate::application::use_cases::{
-    get_holidays::GetHolidaysUseCase, scrape_holidays::ScrapeHolidaysUseCase,
+    backfill_holidays::BackfillHolidaysUseCase, get_health::GetHealthUseCase,
+    get_holidays::GetHolidaysUseCase, manage_api_keys::ManageApiKeysUseCase,
+    manage_overrides::ManageOverridesUseCase, rate_limit::RateLimitUseCase,
+    scheduled_jobs::ScheduledJobsUseCase, scrape_jobs::ScrapeJobsUseCase,
 };
+use crate::domain::entities::api_key::ApiKeyScope;
+use crate::infrastructure::web::handlers::admin_handler::{
+    accept_layout, add_holiday, create_api_key, delete_override, edit_holiday,
+    get_scheduler_status, get_usage, list_api_keys, list_overrides, remove_holiday, revoke_api_key,
+    run_scheduled_job,
+};
+use crate::infrastructure::web::handlers::health_handler::get_health;
 use crate::infrastructure::web::handlers::holiday_handler::{
-    get_holidays, get_holidays_grouped, root, scrape_holidays,
+    get_available_years, get_holidays, get_holidays_by_date, get_holidays_grouped,
+    get_holidays_in_range, get_holidays_today, get_upcoming_holidays, root, scrape_holidays,
+    scrape_holidays_range,
+};
+use crate::infrastructure::web::handlers::job_handler::{get_job, submit_scrape_job};
+use crate::infrastructure::web::middleware::api_key_auth::{require_scope, AccessControl};
+use axum::{
+    middleware,
+    routing::{delete, get, post},
+    Extension, Router,
 };
-use axum::{routing::get, Extension, Router};
 use std::sync::Arc;
 
+// Builds the admin routes. Access is checked by create_routes, which requires the admin scope for them.
+pub fn create_admin_routes(
+    manage_overrides_use_case: Arc<ManageOverridesUseCase>,
+    manage_api_keys_use_case: Arc<ManageApiKeysUseCase>,
+    rate_limit_use_case: Arc<RateLimitUseCase>,
+    scheduled_jobs_use_case: Arc<ScheduledJobsUseCase>,
+) -> Router {
+    Router::new()
+        .route(
+            "/admin/libur/{year}",
+            get(list_overrides)
+                .post(add_holiday)
+                .put(edit_holiday)
+                .delete(remove_holiday),
+        )
+        .route(
+            "/admin/libur/{year}/overrides/{id}",
+            delete(delete_override),
+        )
+        .route("/admin/keys", get(list_api_keys).post(create_api_key))
+        .route("/admin/keys/{id}", delete(revoke_api_key))
+        .route("/admin/usage", get(get_usage))
+        .route("/admin/scheduler", get(get_scheduler_status))
+        .route("/admin/scheduler/{job}/run", post(run_scheduled_job))
+        .route("/admin/layout/accept", post(accept_layout))
+        .layer(Extension(manage_overrides_use_case))
+        .layer(Extension(manage_api_keys_use_case))
+        .layer(Extension(rate_limit_use_case))
+        .layer(Extension(scheduled_jobs_use_case))
+}
+
+// Builds the application routes. Every route group requires an API key scope, and is rate limited per scope:
+// read for holiday data, jobs and health, scrape for triggering scrapes, and admin for the admin routes.
 pub fn create_routes(
     get_holidays_use_case: Arc<GetHolidaysUseCase>,
-    scrape_holidays_use_case: Arc<ScrapeHolidaysUseCase>,
+    get_health_use_case: Arc<GetHealthUseCase>,
+    backfill_holidays_use_case: Arc<BackfillHolidaysUseCase>,
+    scrape_jobs_use_case: Arc<ScrapeJobsUseCase>,
+    admin_routes: Router,
+    access: AccessControl,
 ) -> Router {
-    Router::new()
-        .route("/", get(root))
-        .route("/scrape/{year}", get(scrape_holidays))
+    let guard = |scope| middleware::from_fn_with_state(access.guard(scope), require_scope);
+
+    let read_routes = Router::new()
+        .route("/health", get(get_health))
+        .route("/jobs/{id}", get(get_job))
+        .route("/libur", get(get_holidays_in_range))
+        .route("/libur/years", get(get_available_years))
+        .route("/libur/date/{date}", get(get_holidays_by_date))
+        .route("/libur/today", get(get_holidays_today))
+        .route("/libur/upcoming", get(get_upcoming_holidays))
         .route("/libur/{year}", get(get_holidays))
         .route("/libur/{year}/grouped", get(get_holidays_grouped))
+        .route_layer(guard(ApiKeyScope::Read));
+
+    let scrape_routes = Router::new()
+        .route("/scrape", post(scrape_holidays_range))
+        .route("/scrape/{year}", get(scrape_holidays))
+        .route("/jobs/scrape", post(submit_scrape_job))
+        .route_layer(guard(ApiKeyScope::Scrape));
+
+    Router::new()
+        .route("/", get(root))
+        .merge(read_routes)
+        .merge(scrape_routes)
+        .merge(admin_routes.route_layer(guard(ApiKeyScope::Admin)))
         .layer(Extension(get_holidays_use_case))
-        .layer(Extension(scrape_holidays_use_case))
+        .layer(Extension(get_health_use_case))
+        .layer(Extension(backfill_holidays_use_case))
+        .layer(Extension(scrape_jobs_use_case))
 }
@@ -1,17 +1,38 @@
 use calendar_indonesia::{
     application::use_cases::{
-        get_holidays::GetHolidaysUseCase, scrape_holidays::ScrapeHolidaysUseCase,
+        backfill_holidays::BackfillHolidaysUseCase, get_health::GetHealthUseCase,
+        get_holidays::GetHolidaysUseCase, manage_api_keys::ManageApiKeysUseCase,
+        manage_overrides::ManageOverridesUseCase, rate_limit::RateLimitUseCase,
+        scheduled_jobs::ScheduledJobsUseCase, scrape_holidays::ScrapeHolidaysUseCase,
+        scrape_jobs::ScrapeJobsUseCase,
     },
     config::Config,
+    domain::entities::holiday_merge::MergeStrategy,
     infrastructure::{
-        external::scraper_service::WebScrapingService,
-        persistence::file_repository::FileHolidayRepository,
-        scheduler::holiday_scheduler::start_periodic_scraper,
-        web::routes::holiday_routes::create_routes,
+        external::{
+            circuit_breaker::CircuitBreakerScrapingService, scraper_service::WebScrapingService,
+        },
+        persistence::repository_factory::{create_leader_election, create_repositories},
+        scheduler::{
+            bootstrap::start_bootstrap, job_scheduler::start_scheduler,
+            job_worker::start_job_worker,
+        },
+        web::{
+            middleware::{api_key_auth::AccessControl, client_ip::TrustedProxies},
+            routes::holiday_routes::{create_admin_routes, create_routes},
+        },
+    },
+    shared::utils::{
+        date_utils::parse_timezone,
+        shutdown::{shutdown_channel, wait_for_signal},
     },
 };
+use std::future::IntoFuture;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::time::{timeout_at, Instant};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -21,25 +42,142 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Print startup information to the console
     println!("Starting Holiday API server...");
     println!("Server will run at http://{}:{}", config.host, config.port);
+    println!("Service time zone: {}", config.timezone);
+
+    // Read the service time zone, which the scheduler, "today" and every recorded timestamp use regardless of
+    // the host's time zone
+    let timezone = parse_timezone(&config.timezone).map_err(|e| e.for_setting("TIMEZONE"))?;
 
-    // Setup the repository for storing holiday data (uses File-based storage)
-    let holiday_repository = Arc::new(FileHolidayRepository::new(config.data_dir.clone()));
+    // Setup the repositories for the holiday data, the manual corrections kept apart from it, the API keys
+    // created over the API (only their hashes are stored), the queue of background scrape jobs and the run
+    // history of the scheduled jobs
+    // (file-based, SQLite or PostgreSQL, selected by STORAGE)
+    let repositories = create_repositories(&config, timezone).await?;
+    let holiday_repository = repositories.holidays;
+    let override_repository = repositories.overrides;
+    let api_key_repository = repositories.api_keys;
+    let job_repository = repositories.scrape_jobs;
+    let job_run_repository = repositories.job_runs;
 
-    // Setup the web scraping service to gather holidays from an external source
-    let scraping_service = Arc::new(WebScrapingService::new());
+    // Setup the web scraping service to gather holidays from an external source,
+    // guarded by a circuit breaker so repeated failures fail fast
+    let scraping_service = Arc::new(CircuitBreakerScrapingService::new(
+        Arc::new(WebScrapingService::new(&config, timezone)?),
+        &config,
+        timezone,
+    ));
+
+    // Read how scrapes are merged into the stored holidays, keeping the manual corrections by default
+    let merge_strategy = ScrapeHolidaysUseCase::parse_strategy(Some(&config.scrape_merge_strategy))
+        .map_err(|e| e.for_setting("SCRAPE_MERGE_STRATEGY"))?
+        .unwrap_or(MergeStrategy::MergeKeepManual);
 
     // Setup use cases to interact with the repository and scraping service
-    let get_holidays_use_case = Arc::new(GetHolidaysUseCase::new(holiday_repository.clone()));
+    let get_holidays_use_case = Arc::new(GetHolidaysUseCase::new(
+        holiday_repository.clone(),
+        override_repository.clone(),
+        timezone,
+    ));
     let scrape_holidays_use_case = Arc::new(ScrapeHolidaysUseCase::new(
         holiday_repository.clone(),
-        scraping_service,
+        override_repository.clone(),
+        scraping_service.clone(),
+        merge_strategy,
+    ));
+    let get_health_use_case = Arc::new(GetHealthUseCase::new(scraping_service));
+    let backfill_holidays_use_case = Arc::new(BackfillHolidaysUseCase::new(
+        holiday_repository.clone(),
+        scrape_holidays_use_case.clone(),
+        config.backfill_concurrency,
+    ));
+    let scrape_jobs_use_case = Arc::new(ScrapeJobsUseCase::new(
+        job_repository,
+        scrape_holidays_use_case.clone(),
+        timezone,
+        config.instance_id.clone(),
     ));
+    let scheduled_jobs_use_case = Arc::new(ScheduledJobsUseCase::new(
+        ScheduledJobsUseCase::parse_jobs(&config.scheduler_jobs)?,
+        timezone,
+        config.scheduler_enabled,
+        scrape_holidays_use_case.clone(),
+        holiday_repository.clone(),
+        job_run_repository,
+        create_leader_election(&config)?,
+    ));
+    let manage_overrides_use_case =
+        Arc::new(ManageOverridesUseCase::new(override_repository, timezone));
+    let manage_api_keys_use_case = Arc::new(ManageApiKeysUseCase::new(
+        api_key_repository,
+        config.api_keys.as_deref(),
+        config.admin_token.as_deref(),
+        &config.public_scopes,
+        timezone,
+    )?);
+    let rate_limit_use_case = Arc::new(RateLimitUseCase::new(
+        config.rate_limit_read_per_minute,
+        config.rate_limit_scrape_per_minute,
+        config.rate_limit_admin_per_minute,
+        config.rate_limit_ip_per_minute,
+        config.rate_limit_daily_quota,
+        timezone,
+    ));
+    if !manage_api_keys_use_case.has_admin_key().await? {
+        println!("⚠️  No API key with the admin scope, set ADMIN_TOKEN or API_KEYS to use the admin endpoints");
+    }
 
     // Setup the Axum routes
-    let app = create_routes(get_holidays_use_case, scrape_holidays_use_case.clone());
+    let app = create_routes(
+        get_holidays_use_case,
+        get_health_use_case,
+        backfill_holidays_use_case.clone(),
+        scrape_jobs_use_case.clone(),
+        create_admin_routes(
+            manage_overrides_use_case,
+            manage_api_keys_use_case.clone(),
+            rate_limit_use_case.clone(),
+            scheduled_jobs_use_case.clone(),
+        ),
+        AccessControl::new(
+            manage_api_keys_use_case,
+            rate_limit_use_case,
+            TrustedProxies::parse(&config.trusted_proxies)?,
+        ),
+    );
+
+    // Bootstrap the holiday data in the background: the current and the next year if they have no data yet, where
+    // the next year may not be published yet. This runs on every start, independent of the scheduler and its
+    // leader election
+    let (shutdown_trigger, shutdown) = shutdown_channel();
+    let mut background_tasks = vec![
+        start_bootstrap(
+            backfill_holidays_use_case.clone(),
+            timezone,
+            shutdown.clone(),
+        )
+        .await,
+    ];
 
-    // Start a periodic scraper that will scrape holidays at scheduled intervals
-    start_periodic_scraper(scrape_holidays_use_case.clone()).await;
+    // Start the scheduler that runs the configured jobs (scrapes, pre-fetches, validation) on their cron schedules.
+    // Of several replicas, only the elected leader runs them; it also catches up runs missed while the server was down
+    if config.scheduler_enabled {
+        background_tasks.push(start_scheduler(scheduled_jobs_use_case, shutdown.clone()).await);
+    } else {
+        println!("⏸️  Scheduler disabled (SCHEDULER_ENABLED=false)");
+    }
+
+    // Start the background worker that runs queued scrape jobs
+    background_tasks.push(start_job_worker(scrape_jobs_use_case, shutdown.clone()).await);
+
+    // Start the shutdown when SIGTERM or SIGINT is received
+    tokio::spawn(async move {
+        let signal = wait_for_signal().await;
+        println!(
+            "🛑 Received {}, shutting down: no new connections are accepted, in-flight requests are drained",
+            signal
+        );
+        shutdown_trigger.trigger();
+    });
 
     // Start the server
     let addr = format!("{}:{}", config.host, config.port);
@@ -48,12 +186,78 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Server started successfully at http://{}", addr);
     println!("📚 Available endpoints:");
     println!("   GET  /                     - Welcome message");
-    println!("   GET  /scrape/{{year}}        - Scrape holidays for a specific year");
+    println!("   GET  /health               - Health of the scraper and the external source");
+    println!("   POST /scrape?from=&to=     - Scrape holidays for a range of years");
+    println!(
+        "   GET  /scrape/{{year}}        - Deprecated, queues a scrape job like POST /jobs/scrape"
+    );
+    println!("   POST /jobs/scrape          - Queue a background scrape job");
+    println!("   GET  /jobs/{{id}}            - Get the status of a scrape job");
+    println!("   GET  /libur?from=&to=      - Get holidays between two dates, across years");
+    println!("   GET  /libur/years          - List the years that have holiday data");
+    println!("   GET  /libur/date/{{date}}    - Get holidays on a specific date");
+    println!("   GET  /libur/today?tz=      - Whether today is a holiday, in a time zone (WIB, WITA, WIT, ...)");
+    println!("   GET  /libur/upcoming?tz=   - Get the next holidays from today");
     println!("   GET  /libur/{{year}}         - Get holidays for a specific year");
-    println!("   GET  /libur/{{year}}/grouped - Get holidays for a specific year, grouped by type");
+    println!("   *    /admin/libur/{{year}}   - Add, edit or remove holidays (admin scope)");
+    println!("   *    /admin/keys           - List, create or revoke API keys (admin scope)");
+    println!("   GET  /admin/usage          - Daily request counts per client (admin scope)");
+    println!(
+        "   GET  /admin/scheduler      - Scheduled jobs with their next and last run (admin scope)"
+    );
+    println!("   POST /admin/scheduler/{{job}}/run - Queue a run of a scheduled job (admin scope, leader only)");
+    println!("   POST /admin/layout/accept  - Accept a changed layout of the external site (admin scope)");
+    println!("   GET  /libur/{{year}}/grouped - Get holidays for a specific year, grouped by type, month, ...");
+
+    // Serve the app with the client addresses, which identify anonymous clients for rate limiting.
+    // On shutdown the listener is closed and in-flight requests are drained
+    let server_shutdown = shutdown.clone();
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move { server_shutdown.wait().await })
+    .into_future();
+    tokio::pin!(server);
+    let server_stopped = tokio::select! {
+        result = &mut server => {
+            result?;
+            true
+        }
+        _ = shutdown.wait() => false,
+    };
+
+    // The shutdown timeout is a single deadline from the signal, shared by the in-flight requests and the
+    // background tasks, which drain at the same time
+    let shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    let deadline = Instant::now() + shutdown_timeout;
+    if !server_stopped {
+        match timeout_at(deadline, server).await {
+            Ok(result) => result?,
+            Err(_) => println!(
+                "⚠️  In-flight requests did not finish within {}s, closing them",
+                shutdown_timeout.as_secs()
+            ),
+        }
+    }
+    println!("✅ HTTP server stopped");
 
-    // Serve the app
-    axum::serve(listener, app).await?;
+    // Let the bootstrap, the scheduler and the job worker finish the work in progress, until the same deadline.
+    // A scrape job still running after it is left as running in the job store and requeued on the next start
+    let drain = async {
+        for task in background_tasks {
+            if let Err(e) = task.await {
+                println!("❌ Background task failed: {}", e);
+            }
+        }
+    };
+    if timeout_at(deadline, drain).await.is_err() {
+        println!(
+            "⚠️  Background tasks did not finish within {}s; interrupted scrape jobs are requeued on the next start",
+            shutdown_timeout.as_secs()
+        );
+    }
 
+    println!("👋 Shutdown complete");
     Ok(())
 }
@@ -8,13 +8,43 @@ use std::fmt;
 pub type AppResult<T> = Result<T, AppError>;
 
 // Enum defining different error types in the application
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum AppError {
-    NotFound(String),        // 404 error: Resource not found
-    InternalServer(String),  // 500 error: Internal server issues
-    BadRequest(String),      // 400 error: Bad request from client
-    ExternalService(String), // 502 error: External service failure
-    Serialization(String),   // Error during serialization (e.g., JSON parsing)
+    NotFound(String),           // 404 error: Resource not found
+    InternalServer(String),     // 500 error: Internal server issues
+    BadRequest(String),         // 400 error: Bad request from client
+    Forbidden(String),          // 403 error: Action refused by policy (e.g., disallowed by robots.txt)
+    ExternalService(String),    // 502 error: External service failure
+    ServiceUnavailable(String), // 503 error: Dependency temporarily unavailable (e.g., circuit open)
+    Serialization(String),      // Error during serialization (e.g., JSON parsing)
+    Configuration(String),      // 500 error: Invalid configuration (e.g., unknown STORAGE)
+}
+
+impl AppError {
+    // Returns the HTTP status that corresponds to the error variant.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::InternalServer(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::ExternalService(_) => StatusCode::BAD_GATEWAY,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Configuration(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    // Turns an error parsing the value of a setting, e.g. a BadRequest from a parser shared with request input,
+    // into a Configuration error naming the setting. Other errors are returned unchanged.
+    pub fn for_setting(self, setting: &str) -> AppError {
+        match self {
+            AppError::BadRequest(message) => {
+                AppError::Configuration(format!("Invalid {}: {}", setting, message))
+            }
+            other => other,
+        }
+    }
 }
 
 // Implementing Display trait for AppError to format error messages as strings
@@ -24,22 +54,32 @@ impl fmt::Display for AppError {
             AppError::NotFound(msg) => write!(f, "Not Found: {}", msg),
             AppError::InternalServer(msg) => write!(f, "Internal Server Error: {}", msg),
             AppError::BadRequest(msg) => write!(f, "Bad Request: {}", msg),
+            AppError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             AppError::ExternalService(msg) => write!(f, "External Service Error: {}", msg),
+            AppError::ServiceUnavailable(msg) => write!(f, "Service Unavailable: {}", msg),
             AppError::Serialization(msg) => write!(f, "Serialization Error: {}", msg),
+            AppError::Configuration(msg) => write!(f, "Configuration Error: {}", msg),
         }
     }
 }
 
+// Implementing Error trait so AppError can be propagated with `?` in functions returning Box<dyn Error>
+impl std::error::Error for AppError {}
+
 // Implementing IntoResponse trait to convert AppError into an HTTP response
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         // Map error to corresponding HTTP status and message
-        let (status, message) = match self {
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::InternalServer(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            AppError::ExternalService(msg) => (StatusCode::BAD_GATEWAY, msg),
-            AppError::Serialization(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+        let status = self.status_code();
+        let message = match self {
+            AppError::NotFound(msg)
+            | AppError::BadRequest(msg)
+            | AppError::Forbidden(msg)
+            | AppError::InternalServer(msg)
+            | AppError::ExternalService(msg)
+            | AppError::ServiceUnavailable(msg)
+            | AppError::Serialization(msg)
+            | AppError::Configuration(msg) => msg,
         };
 
         // Create a JSON body for the error response
@@ -77,3 +117,11 @@ impl From<reqwest::Error> for AppError {
         AppError::ExternalService(format!("HTTP Request Error: {}", err))
     }
 }
+
+// Converting sqlx::Error to AppError
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        // Wrap database error as InternalServer error
+        AppError::InternalServer(format!("Database Error: {}", err))
+    }
+}
@@ -1,13 +1,85 @@
-use chrono::{Datelike, Local, NaiveDate};
+use crate::shared::errors::app_error::{AppError, AppResult};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc, Weekday};
+use chrono_tz::Tz;
 
-// Retrieves the current year based on the local system time.
-pub fn get_current_year() -> i32 {
-    Local::now().year()
+// The Indonesian time zones, by their common abbreviation.
+const INDONESIAN_TIMEZONES: [(&str, Tz); 3] = [
+    ("WIB", Tz::Asia__Jakarta),
+    ("WITA", Tz::Asia__Makassar),
+    ("WIT", Tz::Asia__Jayapura),
+];
+
+// Retrieves the current year in the given time zone.
+pub fn get_current_year(timezone: Tz) -> i32 {
+    today_in(timezone).year()
+}
+
+// Retrieves today's date in the given time zone, independent of the host's time zone.
+pub fn today_in(timezone: Tz) -> NaiveDate {
+    now_in(timezone).date()
+}
+
+// Retrieves the current date and time in the given time zone, independent of the host's time zone.
+// This is the service's one clock: every timestamp it records (scrapes, jobs, overrides, API keys, saves)
+// is taken from it in the service time zone, so they compare with each other and with the schedules.
+pub fn now_in(timezone: Tz) -> NaiveDateTime {
+    Utc::now().with_timezone(&timezone).naive_local()
+}
+
+// Parses a time zone given as an IANA name (e.g. "Asia/Makassar") or as an Indonesian abbreviation
+// ("WIB", "WITA" or "WIT", in any case).
+pub fn parse_timezone(value: &str) -> AppResult<Tz> {
+    let value = value.trim();
+    if let Some((_, timezone)) = INDONESIAN_TIMEZONES
+        .iter()
+        .find(|(abbreviation, _)| abbreviation.eq_ignore_ascii_case(value))
+    {
+        return Ok(*timezone);
+    }
+
+    value.parse().map_err(|_| {
+        AppError::BadRequest(format!(
+            "Invalid timezone '{}', expected an IANA name such as Asia/Jakarta or one of: WIB, WITA, WIT",
+            value
+        ))
+    })
 }
 
 // Validates if the given year is between 1900 and 2100.
 pub fn is_valid_year(year: i32) -> bool {
-    year >= 1900 && year <= 2100
+    (1900..=2100).contains(&year)
+}
+
+// Parses a date in the "YYYY-MM-DD" format, as used throughout the API.
+pub fn parse_date(value: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+        AppError::BadRequest(format!(
+            "Invalid date '{}', expected YYYY-MM-DD: {}",
+            value, e
+        ))
+    })
+}
+
+// Parses a weekday given as an English or Indonesian name (full or three-letter, e.g. "fri", "jumat")
+// or as an ISO number (1 = Monday ... 7 = Sunday). Returns None if the value is not a weekday.
+pub fn parse_weekday(value: &str) -> Option<Weekday> {
+    let value = value.trim().to_lowercase();
+    let weekday = match value.as_str() {
+        "1" | "senin" | "sen" => Weekday::Mon,
+        "2" | "selasa" | "sel" => Weekday::Tue,
+        "3" | "rabu" | "rab" => Weekday::Wed,
+        "4" | "kamis" | "kam" => Weekday::Thu,
+        "5" | "jumat" | "jum'at" | "jum" => Weekday::Fri,
+        "6" | "sabtu" | "sab" => Weekday::Sat,
+        "7" | "minggu" | "min" => Weekday::Sun,
+        other => return other.parse().ok(),
+    };
+    Some(weekday)
+}
+
+// Checks whether a date falls on a Saturday or Sunday.
+pub fn is_weekend(date: &NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
 }
 
 // Formats a date into the Indonesian format, e.g., "15 Agustus 2023".
@@ -36,3 +108,30 @@ pub fn format_date_indonesia(date: &NaiveDate) -> String {
     // Return the formatted date as "day month year"
     format!("{} {} {}", date.day(), month_name, date.year())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timezone_accepts_indonesian_abbreviations_in_any_case() {
+        assert_eq!(parse_timezone("WIB").unwrap(), Tz::Asia__Jakarta);
+        assert_eq!(parse_timezone(" wita ").unwrap(), Tz::Asia__Makassar);
+        assert_eq!(parse_timezone("Wit").unwrap(), Tz::Asia__Jayapura);
+    }
+
+    #[test]
+    fn parse_timezone_accepts_iana_names() {
+        assert_eq!(parse_timezone("Asia/Makassar").unwrap(), Tz::Asia__Makassar);
+        assert_eq!(parse_timezone("UTC").unwrap(), Tz::UTC);
+    }
+
+    #[test]
+    fn parse_timezone_rejects_unknown_zones() {
+        assert!(matches!(
+            parse_timezone("Asia/Atlantis"),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(parse_timezone("").is_err());
+    }
+}
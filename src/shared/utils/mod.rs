@@ -1 +1,3 @@
-pub mod date_utils;
\ No newline at end of file
+pub mod date_utils;
+pub mod shutdown;
+pub mod text_utils;
\ No newline at end of file
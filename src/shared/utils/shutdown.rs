@@ -0,0 +1,70 @@
+use tokio::sync::watch;
+
+// Shutdown tells background tasks that the server is shutting down.
+// It is cheap to clone; every task keeps its own copy and checks it between units of work,
+// so work that has started (a request, a scrape, a scheduled run) is finished rather than cut off.
+#[derive(Clone)]
+pub struct Shutdown {
+    receiver: watch::Receiver<bool>,
+}
+
+// ShutdownTrigger starts the shutdown, notifying every Shutdown created by the same channel.
+pub struct ShutdownTrigger {
+    sender: watch::Sender<bool>,
+}
+
+// Creates a trigger and the Shutdown it notifies.
+pub fn shutdown_channel() -> (ShutdownTrigger, Shutdown) {
+    let (sender, receiver) = watch::channel(false);
+    (ShutdownTrigger { sender }, Shutdown { receiver })
+}
+
+impl ShutdownTrigger {
+    // Starts the shutdown. Triggering more than once has no further effect.
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+}
+
+impl Shutdown {
+    // Whether the shutdown has started.
+    pub fn is_triggered(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    // Waits until the shutdown has started; returns right away if it already has.
+    pub async fn wait(&self) {
+        let mut receiver = self.receiver.clone();
+        // An error means the trigger was dropped without triggering, so the shutdown will never start.
+        if receiver.wait_for(|triggered| *triggered).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+// Waits for SIGINT (Ctrl+C) or, on Unix, SIGTERM (e.g. from `docker stop` or Kubernetes)
+// and returns the name of the signal received.
+pub async fn wait_for_signal() -> &'static str {
+    let interrupt = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => "SIGINT",
+        _ = terminate => "SIGTERM",
+    }
+}
@@ -0,0 +1,12 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+// Normalizes text for searching: lowercases it and strips diacritics and punctuation,
+// so "Mi'raj", "MI'RAJ", "Mi'rāj" and "Miraj" all become "miraj".
+pub fn normalize_for_search(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+}
@@ -0,0 +1,370 @@
+// Integration tests for the PostgreSQL holiday repository.
+// They need a disposable database and are ignored by default; run them with
+// `TEST_DATABASE_URL=postgres://... cargo test --test postgres_repository -- --ignored`.
+use calendar_indonesia::domain::entities::api_key::{ApiKey, ApiKeyScope};
+use calendar_indonesia::domain::entities::holiday::Holiday;
+use calendar_indonesia::domain::entities::holiday_override::{HolidayOverride, OverrideAction};
+use calendar_indonesia::domain::entities::scheduled_job::{JobRun, JobRunStatus};
+use calendar_indonesia::domain::entities::scrape_job::{JobStatus, ScrapeJob};
+use calendar_indonesia::domain::repositories::api_key_repository::ApiKeyRepository;
+use calendar_indonesia::domain::repositories::holiday_override_repository::HolidayOverrideRepository;
+use calendar_indonesia::domain::repositories::holiday_repository::HolidayRepository;
+use calendar_indonesia::domain::repositories::job_run_repository::JobRunRepository;
+use calendar_indonesia::domain::repositories::scrape_job_repository::ScrapeJobRepository;
+use calendar_indonesia::infrastructure::persistence::postgres_api_key_repository::PostgresApiKeyRepository;
+use calendar_indonesia::infrastructure::persistence::postgres_job_run_repository::PostgresJobRunRepository;
+use calendar_indonesia::infrastructure::persistence::postgres_override_repository::PostgresHolidayOverrideRepository;
+use calendar_indonesia::infrastructure::persistence::postgres_repository::PostgresHolidayRepository;
+use calendar_indonesia::infrastructure::persistence::postgres_scrape_job_repository::PostgresScrapeJobRepository;
+use calendar_indonesia::shared::errors::app_error::AppError;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use std::sync::Arc;
+
+// Years far outside the scraped range, so the tests never touch real data.
+// Tests run in parallel, so each one works on its own years.
+const QUERY_YEARS: [i32; 3] = [1901, 1902, 1903];
+const CONCURRENT_YEAR: i32 = 1904;
+const UPDATE_YEAR: i32 = 1905;
+const OVERRIDE_YEAR: i32 = 1906;
+
+async fn connect() -> PostgresHolidayRepository {
+    let url = std::env::var("TEST_DATABASE_URL")
+        .expect("TEST_DATABASE_URL must point to a disposable PostgreSQL database");
+    PostgresHolidayRepository::new(&url, 5, Tz::Asia__Jakarta)
+        .await
+        .expect("failed to connect and migrate")
+}
+
+async fn clean(repository: &PostgresHolidayRepository, years: &[i32]) {
+    for &year in years {
+        let _ = repository.delete_year(year).await;
+    }
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn holiday(year: i32, month: u32, day: u32, description: &str) -> Holiday {
+    Holiday::new(date(year, month, day), description.to_string(), false)
+}
+
+#[tokio::test]
+#[ignore]
+async fn migrations_are_idempotent() {
+    // The second connection finds every migration applied and must not fail re-applying them.
+    let _first = connect().await;
+    let second = connect().await;
+
+    assert!(second.list_available_years().await.is_ok());
+}
+
+#[tokio::test]
+#[ignore]
+async fn save_replaces_the_year_and_queries_by_range_and_date() {
+    let repository = connect().await;
+    clean(&repository, &QUERY_YEARS).await;
+
+    repository
+        .save_holidays(vec![holiday(1901, 1, 1, "Old")], 1901)
+        .await
+        .unwrap();
+    repository
+        .save_holidays(
+            vec![
+                holiday(1901, 12, 25, "Christmas"),
+                holiday(1901, 1, 1, "New Year"),
+            ],
+            1901,
+        )
+        .await
+        .unwrap();
+    repository
+        .save_holidays(vec![holiday(1902, 1, 1, "New Year")], 1902)
+        .await
+        .unwrap();
+
+    let stored = repository.get_holidays_by_year(1901).await.unwrap();
+    let descriptions: Vec<_> = stored.iter().map(|h| h.description.as_str()).collect();
+    assert_eq!(descriptions, ["New Year", "Christmas"]);
+
+    let range = repository
+        .get_holidays_in_range(date(1901, 12, 1), date(1902, 1, 1))
+        .await
+        .unwrap();
+    let dates: Vec<_> = range.iter().map(|h| h.date).collect();
+    assert_eq!(dates, [date(1901, 12, 25), date(1902, 1, 1)]);
+
+    let on_date = repository.find_by_date(date(1901, 1, 1)).await.unwrap();
+    assert_eq!(on_date.len(), 1);
+    assert_eq!(on_date[0].description, "New Year");
+    assert!(repository
+        .find_by_date(date(1901, 1, 2))
+        .await
+        .unwrap()
+        .is_empty());
+
+    // An empty year is still a saved year.
+    repository.save_holidays(Vec::new(), 1903).await.unwrap();
+    assert!(repository.holidays_exist_for_year(1903).await.unwrap());
+    assert!(repository
+        .get_holidays_by_year(1903)
+        .await
+        .unwrap()
+        .is_empty());
+
+    clean(&repository, &QUERY_YEARS).await;
+    assert!(repository.get_holidays_by_year(1901).await.is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn concurrent_saves_of_a_year_never_interleave() {
+    let repository = Arc::new(connect().await);
+    clean(&repository, &[CONCURRENT_YEAR]).await;
+
+    let mut tasks = Vec::new();
+    for writer in 0..8u32 {
+        let repository = repository.clone();
+        tasks.push(tokio::spawn(async move {
+            let holidays = (1..=10)
+                .map(|day| holiday(CONCURRENT_YEAR, 1, day, &format!("writer {}", writer)))
+                .collect();
+            repository
+                .save_holidays(holidays, CONCURRENT_YEAR)
+                .await
+                .unwrap();
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    // Every save is one transaction, so the year holds exactly one writer's holidays.
+    let stored = repository
+        .get_holidays_by_year(CONCURRENT_YEAR)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 10);
+    assert!(stored
+        .iter()
+        .all(|h| h.description == stored[0].description));
+
+    clean(&repository, &[CONCURRENT_YEAR]).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn concurrent_updates_of_a_year_are_all_kept() {
+    let repository = Arc::new(connect().await);
+    clean(&repository, &[UPDATE_YEAR]).await;
+
+    let mut tasks = Vec::new();
+    for day in 1..=10 {
+        let repository = repository.clone();
+        tasks.push(tokio::spawn(async move {
+            repository
+                .update_year(
+                    UPDATE_YEAR,
+                    Box::new(move |mut holidays| {
+                        holidays.push(holiday(UPDATE_YEAR, 1, day, "Added"));
+                        holidays
+                    }),
+                )
+                .await
+                .unwrap();
+        }));
+    }
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    // Each update reads the year under its lock, so none of them overwrites another.
+    let stored = repository.get_holidays_by_year(UPDATE_YEAR).await.unwrap();
+    assert_eq!(stored.len(), 10);
+
+    clean(&repository, &[UPDATE_YEAR]).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn overrides_are_stored_listed_and_deleted_per_year() {
+    let overrides = PostgresHolidayOverrideRepository::new(connect().await.pool());
+    for holiday_override in overrides.list_overrides(OVERRIDE_YEAR).await.unwrap() {
+        let _ = overrides
+            .delete_override(OVERRIDE_YEAR, &holiday_override.id)
+            .await;
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    overrides
+        .save_override(HolidayOverride {
+            id: id.clone(),
+            year: OVERRIDE_YEAR,
+            date: date(OVERRIDE_YEAR, 1, 1),
+            action: OverrideAction::Edit,
+            match_description: Some("Old".to_string()),
+            description: Some("New".to_string()),
+            is_joint_leave: None,
+            created_at: date(OVERRIDE_YEAR, 1, 1).and_hms_opt(8, 0, 0).unwrap(),
+        })
+        .await
+        .unwrap();
+
+    let stored = overrides.list_overrides(OVERRIDE_YEAR).await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].action, OverrideAction::Edit);
+    assert_eq!(stored[0].description.as_deref(), Some("New"));
+    assert_eq!(stored[0].is_joint_leave, None);
+    assert!(overrides
+        .list_override_years()
+        .await
+        .unwrap()
+        .contains(&OVERRIDE_YEAR));
+
+    // The id alone is not enough: the override must belong to the year.
+    assert!(matches!(
+        overrides.delete_override(OVERRIDE_YEAR + 1, &id).await,
+        Err(AppError::NotFound(_))
+    ));
+    overrides.delete_override(OVERRIDE_YEAR, &id).await.unwrap();
+    assert!(overrides
+        .list_overrides(OVERRIDE_YEAR)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+#[ignore]
+async fn api_keys_are_stored_found_by_hash_and_deleted() {
+    let api_keys = PostgresApiKeyRepository::new(connect().await.pool());
+    // A random id and secret, so the test never clashes with other keys in the database.
+    let id = uuid::Uuid::new_v4().to_string();
+    let key_hash = ApiKey::hash_secret(&id);
+
+    api_keys
+        .save_key(ApiKey {
+            id: id.clone(),
+            name: "integration-test".to_string(),
+            key_hash: key_hash.clone(),
+            scopes: vec![ApiKeyScope::Read, ApiKeyScope::Scrape],
+            created_at: date(2025, 1, 1).and_hms_opt(8, 0, 0).unwrap(),
+        })
+        .await
+        .unwrap();
+
+    let found = api_keys.find_by_hash(&key_hash).await.unwrap().unwrap();
+    assert_eq!(found.id, id);
+    assert_eq!(found.scopes, [ApiKeyScope::Read, ApiKeyScope::Scrape]);
+    assert!(api_keys
+        .list_keys()
+        .await
+        .unwrap()
+        .iter()
+        .any(|api_key| api_key.id == id));
+
+    api_keys.delete_key(&id).await.unwrap();
+    assert!(api_keys.find_by_hash(&key_hash).await.unwrap().is_none());
+    assert!(matches!(
+        api_keys.delete_key(&id).await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+#[ignore]
+async fn scrape_jobs_are_claimed_once_and_only_the_owners_jobs_are_requeued() {
+    let repository = connect().await;
+    let jobs = Arc::new(PostgresScrapeJobRepository::new(repository.pool()));
+    let now = date(2025, 1, 1).and_hms_opt(0, 0, 0).unwrap();
+    // Each run has its own owners, and jobs left queued by an earlier run are drained first.
+    let owner = uuid::Uuid::new_v4().to_string();
+    let other = uuid::Uuid::new_v4().to_string();
+    while jobs.claim_next_job(&other, now).await.unwrap().is_some() {}
+
+    let ids: Vec<String> = (0..4).map(|_| uuid::Uuid::new_v4().to_string()).collect();
+    for id in &ids {
+        let job = ScrapeJob::new(id.clone(), 1907, None, now);
+        jobs.save_job(job).await.unwrap();
+    }
+
+    let claims = (0..8).map(|i| {
+        let jobs = jobs.clone();
+        let owner = if i % 2 == 0 {
+            owner.clone()
+        } else {
+            other.clone()
+        };
+        tokio::spawn(async move { jobs.claim_next_job(&owner, now).await.unwrap() })
+    });
+    let mut claimed = Vec::new();
+    for claim in claims {
+        if let Some(job) = claim.await.unwrap() {
+            claimed.push(job);
+        }
+    }
+    claimed.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut expected = ids.clone();
+    expected.sort();
+    assert_eq!(
+        claimed.iter().map(|job| job.id.clone()).collect::<Vec<_>>(),
+        expected
+    );
+
+    let owned = claimed
+        .iter()
+        .filter(|job| job.owner.as_deref() == Some(owner.as_str()))
+        .count();
+    assert_eq!(jobs.requeue_jobs_of(&owner).await.unwrap(), owned);
+    for job in &claimed {
+        let stored = jobs.get_job(&job.id).await.unwrap().unwrap();
+        let expected = if job.owner.as_deref() == Some(owner.as_str()) {
+            JobStatus::Queued
+        } else {
+            JobStatus::Running
+        };
+        assert_eq!(stored.status, expected);
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn job_runs_are_listed_newest_first_and_the_last_success_is_found() {
+    let repository = connect().await;
+    let runs = PostgresJobRunRepository::new(repository.pool());
+    // Each run of the test records runs of its own job.
+    let job = format!("test-{}", uuid::Uuid::new_v4());
+    let run = |day: u32, status: JobRunStatus| {
+        let at = date(2025, 1, day).and_hms_opt(0, 0, 0).unwrap();
+        JobRun {
+            id: uuid::Uuid::new_v4().to_string(),
+            job: job.clone(),
+            scheduled_for: at,
+            started_at: at,
+            finished_at: at,
+            status,
+            message: format!("run of day {}", day),
+        }
+    };
+
+    runs.save_run(run(1, JobRunStatus::Succeeded))
+        .await
+        .unwrap();
+    runs.save_run(run(2, JobRunStatus::Skipped)).await.unwrap();
+    runs.save_run(run(3, JobRunStatus::Failed)).await.unwrap();
+
+    let listed = runs.list_runs(Some(&job), 2).await.unwrap();
+    let last_success = runs.last_successful_run(&job).await.unwrap().unwrap();
+
+    assert_eq!(
+        listed
+            .iter()
+            .map(|run| run.message.as_str())
+            .collect::<Vec<_>>(),
+        vec!["run of day 3", "run of day 2"]
+    );
+    assert_eq!(listed[0].status, JobRunStatus::Failed);
+    assert_eq!(last_success.message, "run of day 2");
+}